@@ -0,0 +1,31 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+use crate::CommandExecute;
+use clap::CommandFactory;
+use clap_complete::Shell;
+
+/// Print a shell completion script for `cargo pgx`
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct Completions {
+    /// The shell to generate completions for
+    #[clap(value_enum)]
+    shell: Shell,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+impl CommandExecute for Completions {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let mut command = crate::CargoCommand::command();
+        clap_complete::generate(self.shell, &mut command, "cargo", &mut std::io::stdout());
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::command::start::port_in_use;
+use crate::command::status::status_postgres;
+use crate::CommandExecute;
+use owo_colors::OwoColorize;
+use pgx_pg_config::{PgConfig, Pgx};
+use std::path::Path;
+
+/// Check the pgx setup for common problems, and suggest how to fix them
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct Doctor {
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+impl CommandExecute for Doctor {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let mut doctor = Checklist::default();
+
+        doctor.check_pgx_home();
+        if std::env::var("PGX_PG_CONFIG_PATH").is_ok() {
+            println!(
+                "{} `PGX_PG_CONFIG_PATH` is set, so other commands will use it instead of config.toml",
+                "  note".bold().yellow()
+            );
+        }
+
+        match Pgx::configured_pg_configs() {
+            Ok(pg_configs) => {
+                doctor.pass(format!("config.toml lists {} Postgres version(s)", pg_configs.len()));
+                for (label, pg_config) in &pg_configs {
+                    if doctor.check_pg_config(label, pg_config) {
+                        doctor.check_ports(label, pg_config);
+                    }
+                }
+            }
+            Err(e) => doctor.fail(format!("{:#}", e), "run `cargo pgx init`"),
+        }
+
+        if doctor.failures > 0 {
+            println!("\n{} check(s) failed", doctor.failures);
+            // We explicitly do not want to return a spantraced error here.
+            std::process::exit(1)
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct Checklist {
+    failures: usize,
+}
+
+impl Checklist {
+    fn pass(&mut self, message: impl AsRef<str>) {
+        println!("{} {}", "    ok".bold().green(), message.as_ref());
+    }
+
+    fn fail(&mut self, message: impl AsRef<str>, hint: impl AsRef<str>) {
+        self.failures += 1;
+        println!("{} {}", "  FAIL".bold().red(), message.as_ref());
+        println!("{} {}", "  hint".bold().cyan(), hint.as_ref());
+    }
+
+    fn check_pgx_home(&mut self) {
+        let hint = "set `PGX_HOME` to a writable directory";
+        let home = match Pgx::home() {
+            Ok(home) => home,
+            Err(e) => return self.fail(format!("PGX_HOME: {}", e), hint),
+        };
+
+        let probe = home.join(".doctor-probe");
+        match std::fs::write(&probe, b"").and_then(|_| std::fs::remove_file(&probe)) {
+            Ok(()) => self.pass(format!("PGX_HOME `{}` is writable", home.display())),
+            Err(e) => {
+                self.fail(format!("PGX_HOME `{}` isn't writable: {}", home.display(), e), hint)
+            }
+        }
+    }
+
+    /// Returns whether `pg_config` is usable, and so its ports can be checked
+    fn check_pg_config(&mut self, label: &str, pg_config: &PgConfig) -> bool {
+        let hint = format!("run `cargo pgx init --{}=/path/to/pg_config`", label);
+        let path = pg_config.path().expect("configured pg_config has no path");
+        if !path.exists() {
+            self.fail(format!("{}: `{}` doesn't exist", label, path.display()), hint);
+            return false;
+        }
+        if !is_executable(&path) {
+            self.fail(format!("{}: `{}` isn't executable", label, path.display()), hint);
+            return false;
+        }
+
+        match pg_config.major_version() {
+            Ok(major) if format!("pg{}", major) == label => {
+                self.pass(format!("{}: `{}` is Postgres {}", label, path.display(), major));
+                true
+            }
+            Ok(major) => {
+                self.fail(
+                    format!(
+                        "{}: `{}` is Postgres {}, not the expected version",
+                        label,
+                        path.display(),
+                        major
+                    ),
+                    hint,
+                );
+                false
+            }
+            Err(e) => {
+                self.fail(format!("{}: `{}` failed to run: {:#}", label, path.display(), e), hint);
+                false
+            }
+        }
+    }
+
+    fn check_ports(&mut self, label: &str, pg_config: &PgConfig) {
+        let hint = "stop whatever is listening there, or change `base_port` and `base_testing_port` in config.toml";
+        let ports = [("port", pg_config.port()), ("test port", pg_config.test_port())];
+        for (name, port) in ports {
+            let port = match port {
+                Ok(port) => port,
+                Err(e) => return self.fail(format!("{}: {:#}", label, e), hint),
+            };
+
+            match port_in_use(port) {
+                Ok(false) => self.pass(format!("{}: {} {} is free", label, name, port)),
+                Ok(true) if name == "port" && status_postgres(pg_config).unwrap_or(false) => self
+                    .pass(format!(
+                        "{}: {} {} is used by pgx's running Postgres",
+                        label, name, port
+                    )),
+                Ok(true) => {
+                    self.fail(format!("{}: {} {} is already in use", label, name, port), hint)
+                }
+                Err(e) => self
+                    .fail(format!("{}: {} {} couldn't be probed: {}", label, name, port, e), hint),
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
@@ -0,0 +1,90 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::command::init::download_dir;
+use crate::CommandExecute;
+use owo_colors::OwoColorize;
+use pgx_pg_config::{get_target_dir, PgConfig, PgConfigSelector, Pgx};
+use std::fmt::Display;
+use std::path::PathBuf;
+
+/// Print the paths pgx resolves, such as `PGX_HOME` and each Postgres' directories
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct Env {
+    /// The Postgres version to print paths for (`pg10`, `pg11`, `pg12`, `pg13`, `pg14`, or `all`)
+    #[clap(env = "PG_VERSION")]
+    pg_version: Option<String>,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+impl CommandExecute for Env {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        // every value is printed, even if some can't be resolved, as the failures are often
+        // exactly what's being debugged
+        print_value("PGX_HOME", Pgx::home().map(display));
+        print_value("config.toml", Pgx::config_toml().map(display));
+        print_value("postmaster stubs", Pgx::postmaster_stub_dir().map(display));
+        print_value("target dir", get_target_dir().map(display));
+
+        let pgx = match Pgx::from_config() {
+            Ok(pgx) => pgx,
+            Err(e) => {
+                print_value::<String, _>("pg_configs", Err(e));
+                return Ok(());
+            }
+        };
+
+        let pg_version = self.pg_version.unwrap_or_else(|| "all".to_string());
+        for pg_config in pgx.iter(PgConfigSelector::new(&pg_version)) {
+            println!();
+            match pg_config {
+                Ok(pg_config) => print_pg_config(pg_config),
+                Err(e) => print_value::<String, _>("pg_config", Err(e)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn print_pg_config(pg_config: &PgConfig) {
+    match pg_config.label() {
+        Ok(label) => println!("{}", label.bold().cyan()),
+        Err(e) => println!("{}", format!("{:#}", e).red()),
+    }
+    print_value("pg_config", pg_config.path().ok_or("not found").map(display));
+    print_value("version", pg_config.version());
+    print_value("port", pg_config.port());
+    print_value("test port", pg_config.test_port());
+    print_value("bindir", pg_config.bin_dir().map(display));
+    print_value("pkglibdir", pg_config.pkglibdir().map(display));
+    print_value("sharedir", pg_config.sharedir().map(display));
+    print_value("extension dir", pg_config.extension_dir().map(display));
+    print_value("includedir-server", pg_config.includedir_server().map(display));
+    let download_dir = Pgx::home()
+        .map_err(eyre::Report::from)
+        .and_then(|pgx_home| download_dir(&pgx_home, pg_config));
+    print_value("download dir", download_dir.map(display));
+    print_value("data dir", pg_config.data_dir().map(display));
+    print_value("log file", pg_config.log_file().map(display));
+}
+
+fn print_value<T: Display, E: Display>(name: &str, value: Result<T, E>) {
+    match value {
+        Ok(value) => println!("{:>18}: {}", name.bold().green(), value),
+        Err(e) => println!("{:>18}: {}", name.bold().red(), format!("{:#}", e).red()),
+    }
+}
+
+fn display(path: PathBuf) -> String {
+    path.display().to_string()
+}
@@ -18,7 +18,8 @@ use std::process::Command;
 #[derive(clap::Args, Debug)]
 #[clap(author)]
 pub(crate) struct Get {
-    /// One of the properties from `$EXTENSION.control`
+    /// One of the properties from `$EXTENSION.control`, or `dependencies` to list the extensions
+    /// it `requires`, one per line
     name: String,
     #[clap(from_global, action = ArgAction::Count)]
     verbose: u8,
@@ -40,7 +41,11 @@ impl CommandExecute for Get {
             crate::manifest::manifest_path(&metadata, self.package.as_ref())
                 .wrap_err("Couldn't get manifest path")?;
 
-        if let Some(value) = get_property(&package_manifest_path, &self.name)? {
+        if self.name == "dependencies" {
+            for dependency in extension_dependencies(&package_manifest_path)? {
+                println!("{}", dependency);
+            }
+        } else if let Some(value) = get_property(&package_manifest_path, &self.name)? {
             println!("{}", value);
         }
         Ok(())
@@ -84,6 +89,23 @@ pub fn get_property(manifest_path: impl AsRef<Path>, name: &str) -> eyre::Result
     Ok(None)
 }
 
+/// The extensions named by the `requires` property of the extension's `.control` file, as it will
+/// be installed, so including any set from `[package.metadata.pgx]` in `Cargo.toml`
+pub(crate) fn extension_dependencies(manifest_path: impl AsRef<Path>) -> eyre::Result<Vec<String>> {
+    let contents = crate::command::install::render_control_file(&manifest_path, None)?;
+    let requires = contents.lines().find_map(|line| match line.split_once('=') {
+        Some((k, v)) if k.trim() == "requires" => Some(v.trim().trim_matches('\'').to_string()),
+        _ => None,
+    });
+
+    Ok(requires
+        .iter()
+        .flat_map(|requires| requires.split(','))
+        .map(|dependency| dependency.trim().to_string())
+        .filter(|dependency| !dependency.is_empty())
+        .collect())
+}
+
 pub(crate) fn find_control_file(
     manifest_path: impl AsRef<Path>,
 ) -> eyre::Result<(PathBuf, String)> {
@@ -20,8 +20,9 @@ use rayon::prelude::*;
 use std::collections::HashMap;
 use std::env;
 use std::fs::File;
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 
 use std::sync::{Arc, Mutex};
@@ -66,11 +67,105 @@ pub(crate) struct Init {
     base_port: Option<u16>,
     #[clap(long, help = "Base testing port number")]
     base_testing_port: Option<u16>,
+    /// Stream the output of Postgres' `configure` and `make` while building downloaded versions
+    #[clap(long)]
+    verbose_build: bool,
+    /// How many jobs `make` may run at once for each downloaded version it builds (default is a
+    /// third of the number of CPUs, as versions are built concurrently)
+    #[clap(long, short)]
+    jobs: Option<usize>,
+    /// How clients must authenticate to the pgx-managed Postgres instances
+    #[clap(long, value_enum, default_value = "trust")]
+    auth: AuthMethod,
+    /// The superuser's password, required when `--auth` isn't `trust`
+    #[clap(long, env = "PGPASSWORD", hide_env_values = true)]
+    password: Option<String>,
+    /// Create the data directory here rather than where pgx manages it.  Requires a single
+    /// Postgres version, and the same `--pgdata` must then be given to `start`, `stop` and `status`
+    #[clap(long, value_parser)]
+    pgdata: Option<PathBuf>,
+}
+
+/// A `pg_hba.conf` authentication method for pgx-managed Postgres instances
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum AuthMethod {
+    Trust,
+    Md5,
+    #[value(name = "scram-sha-256")]
+    ScramSha256,
+}
+
+impl AuthMethod {
+    /// The method's name, as `initdb` and `pg_hba.conf` spell it
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            AuthMethod::Trust => "trust",
+            AuthMethod::Md5 => "md5",
+            AuthMethod::ScramSha256 => "scram-sha-256",
+        }
+    }
+
+    /// Ensure a password was provided if this method requires one
+    pub(crate) fn check_password(&self, password: Option<&str>) -> eyre::Result<()> {
+        match (self, password) {
+            (AuthMethod::Trust, _) | (_, Some(_)) => Ok(()),
+            (_, None) => Err(eyre!(
+                "`--auth {}` requires a password, set one with `--password` or `PGPASSWORD`",
+                self.as_str()
+            )),
+        }
+    }
+}
+
+/// Record the superuser's password for the instance on `pg_config`'s port in
+/// [`Pgx::passfile()`], so the clients pgx runs can authenticate, or forget it if `auth` is `trust`
+pub(crate) fn save_password(
+    pg_config: &PgConfig,
+    auth: AuthMethod,
+    password: Option<&str>,
+) -> eyre::Result<()> {
+    let passfile = Pgx::passfile()?;
+    let prefix = format!("{}:{}:", pg_config.host(), pg_config.port()?);
+    let mut contents = match std::fs::read_to_string(&passfile) {
+        Ok(contents) => contents
+            .lines()
+            .filter(|line| !line.starts_with(&prefix))
+            .map(|line| format!("{}\n", line))
+            .collect::<String>(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).wrap_err_with(|| format!("unable to read `{}`", passfile.display()))
+        }
+    };
+    match (auth, password) {
+        (AuthMethod::Trust, _) | (_, None) => {}
+        (_, Some(password)) => {
+            // `:` and `\` separate and escape a `.pgpass` line's fields
+            let password = password.replace('\\', "\\\\").replace(':', "\\:");
+            contents.push_str(&format!("{}*:*:{}\n", prefix, password));
+        }
+    }
+
+    // libpq ignores a password file that others can read
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(&passfile)
+        .wrap_err_with(|| format!("unable to create `{}`", passfile.display()))?;
+    file.write_all(contents.as_bytes())
+        .wrap_err_with(|| format!("unable to write `{}`", passfile.display()))
 }
 
 impl CommandExecute for Init {
     #[tracing::instrument(level = "error", skip(self))]
     fn execute(self) -> eyre::Result<()> {
+        self.auth.check_password(self.password.as_deref())?;
+        if self.pgdata.is_some() && versions_requested(&self) != 1 {
+            return Err(eyre!("`--pgdata` requires initializing a single Postgres version"));
+        }
+
         let mut versions = HashMap::new();
 
         if let Some(ref version) = self.pg10 {
@@ -119,6 +214,19 @@ impl CommandExecute for Init {
     }
 }
 
+/// How many Postgres versions `init` will set up.  All of them, if none were named
+fn versions_requested(init: &Init) -> usize {
+    let named = [&init.pg10, &init.pg11, &init.pg12, &init.pg13, &init.pg14]
+        .iter()
+        .filter(|version| version.is_some())
+        .count();
+    if named == 0 {
+        SUPPORTED_MAJOR_VERSIONS.len()
+    } else {
+        named
+    }
+}
+
 #[tracing::instrument(skip_all, fields(pgx_home = %Pgx::home()?.display()))]
 pub(crate) fn init_pgx(pgx: &Pgx, init: &Init) -> eyre::Result<()> {
     let dir = Pgx::home()?;
@@ -138,7 +246,8 @@ pub(crate) fn init_pgx(pgx: &Pgx, init: &Init) -> eyre::Result<()> {
             let mut pg_config = pg_config.clone();
             stop_postgres(&pg_config).ok(); // no need to fail on errors trying to stop postgres while initializing
             if !pg_config.is_real() {
-                pg_config = match download_postgres(&pg_config, &dir) {
+                let jobs = init.jobs.unwrap_or_else(|| num_cpus::get() / 3).max(1);
+                pg_config = match download_postgres(&pg_config, &dir, init.verbose_build, jobs) {
                     Ok(pg_config) => pg_config,
                     Err(e) => return Err(eyre!(e)),
                 }
@@ -169,10 +278,14 @@ pub(crate) fn init_pgx(pgx: &Pgx, init: &Init) -> eyre::Result<()> {
         if is_root_user() {
             println!("{} initdb as current user is root user", "   Skipping".bold().green(),);
         } else {
-            let datadir = pg_config.data_dir()?;
+            let datadir = match &init.pgdata {
+                Some(pgdata) => std::env::current_dir()?.join(pgdata),
+                None => pg_config.data_dir()?,
+            };
             let bindir = pg_config.bin_dir()?;
             if !datadir.exists() {
-                initdb(&bindir, &datadir)?;
+                initdb(&bindir, &datadir, init.auth, init.password.as_deref())?;
+                save_password(pg_config, init.auth, init.password.as_deref())?;
             }
         }
     }
@@ -182,7 +295,12 @@ pub(crate) fn init_pgx(pgx: &Pgx, init: &Init) -> eyre::Result<()> {
 }
 
 #[tracing::instrument(level = "error", skip_all, fields(pg_version = %pg_config.version()?, pgx_home))]
-fn download_postgres(pg_config: &PgConfig, pgx_home: &PathBuf) -> eyre::Result<PgConfig> {
+fn download_postgres(
+    pg_config: &PgConfig,
+    pgx_home: &Path,
+    verbose_build: bool,
+    jobs: usize,
+) -> eyre::Result<PgConfig> {
     use env_proxy::for_url_str;
     use ureq::{Agent, AgentBuilder, Proxy};
 
@@ -215,14 +333,20 @@ fn download_postgres(pg_config: &PgConfig, pgx_home: &PathBuf) -> eyre::Result<P
     let mut buf = Vec::new();
     let _count = http_response.into_reader().read_to_end(&mut buf)?;
     let pgdir = untar(&buf, pgx_home, pg_config)?;
-    configure_postgres(pg_config, &pgdir)?;
-    make_postgres(pg_config, &pgdir)?;
-    make_install_postgres(pg_config, &pgdir) // returns a new PgConfig object
+    configure_postgres(pg_config, &pgdir, verbose_build)?;
+    make_postgres(pg_config, &pgdir, verbose_build, jobs)?;
+    make_install_postgres(pg_config, &pgdir, verbose_build) // returns a new PgConfig object
 }
 
-fn untar(bytes: &[u8], pgxdir: &PathBuf, pg_config: &PgConfig) -> eyre::Result<PathBuf> {
-    let mut pgdir = pgxdir.clone();
+/// The directory under `pgx_home` that `pg_config`'s version of Postgres is downloaded and built in
+pub(crate) fn download_dir(pgx_home: &Path, pg_config: &PgConfig) -> eyre::Result<PathBuf> {
+    let mut pgdir = pgx_home.to_path_buf();
     pgdir.push(format!("{}.{}", pg_config.major_version()?, pg_config.minor_version()?));
+    Ok(pgdir)
+}
+
+fn untar(bytes: &[u8], pgxdir: &Path, pg_config: &PgConfig) -> eyre::Result<PathBuf> {
+    let pgdir = download_dir(pgxdir, pg_config)?;
     if pgdir.exists() {
         // delete everything at this path if it already exists
         println!("{} {}", "     Removing".bold().green(), pgdir.display());
@@ -261,7 +385,11 @@ fn untar(bytes: &[u8], pgxdir: &PathBuf, pg_config: &PgConfig) -> eyre::Result<P
     }
 }
 
-fn configure_postgres(pg_config: &PgConfig, pgdir: &PathBuf) -> eyre::Result<()> {
+fn configure_postgres(
+    pg_config: &PgConfig,
+    pgdir: &PathBuf,
+    verbose_build: bool,
+) -> eyre::Result<()> {
     println!(
         "{} Postgres v{}.{}",
         "  Configuring".bold().green(),
@@ -286,29 +414,15 @@ fn configure_postgres(pg_config: &PgConfig, pgdir: &PathBuf) -> eyre::Result<()>
         command.env_remove(var);
     }
 
-    let command_str = format!("{:?}", command);
-    tracing::debug!(command = %command_str, "Running");
-    let child = command.spawn()?;
-    let output = child.wait_with_output()?;
-    tracing::trace!(status_code = %output.status, command = %command_str, "Finished");
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!(
-                "{}\n{}{}",
-                command_str,
-                String::from_utf8(output.stdout).unwrap(),
-                String::from_utf8(output.stderr).unwrap()
-            ),
-        ))?
-    }
+    run_build_command(command, pg_config, pgdir, verbose_build)
 }
 
-fn make_postgres(pg_config: &PgConfig, pgdir: &PathBuf) -> eyre::Result<()> {
-    let num_cpus = 1.max(num_cpus::get() / 3);
+fn make_postgres(
+    pg_config: &PgConfig,
+    pgdir: &PathBuf,
+    verbose_build: bool,
+    jobs: usize,
+) -> eyre::Result<()> {
     println!(
         "{} Postgres v{}.{}",
         "    Compiling".bold().green(),
@@ -319,7 +433,7 @@ fn make_postgres(pg_config: &PgConfig, pgdir: &PathBuf) -> eyre::Result<()> {
 
     command
         .arg("-j")
-        .arg(num_cpus.to_string())
+        .arg(jobs.to_string())
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .stdin(std::process::Stdio::null())
@@ -329,25 +443,14 @@ fn make_postgres(pg_config: &PgConfig, pgdir: &PathBuf) -> eyre::Result<()> {
         command.env_remove(var);
     }
 
-    let command_str = format!("{:?}", command);
-    tracing::debug!(command = %command_str, "Running");
-    let child = command.spawn()?;
-    let output = child.wait_with_output()?;
-    tracing::trace!(status_code = %output.status, command = %command_str, "Finished");
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        Err(eyre!(
-            "{}\n{}{}",
-            command_str,
-            String::from_utf8(output.stdout)?,
-            String::from_utf8(output.stderr)?
-        ))
-    }
+    run_build_command(command, pg_config, pgdir, verbose_build)
 }
 
-fn make_install_postgres(version: &PgConfig, pgdir: &PathBuf) -> eyre::Result<PgConfig> {
+fn make_install_postgres(
+    version: &PgConfig,
+    pgdir: &PathBuf,
+    verbose_build: bool,
+) -> eyre::Result<PgConfig> {
     println!(
         "{} Postgres v{}.{} to {}",
         "   Installing".bold().green(),
@@ -367,23 +470,85 @@ fn make_install_postgres(version: &PgConfig, pgdir: &PathBuf) -> eyre::Result<Pg
         command.env_remove(var);
     }
 
+    run_build_command(command, version, pgdir, verbose_build)?;
+
+    let mut pg_config = get_pg_installdir(pgdir);
+    pg_config.push("bin");
+    pg_config.push("pg_config");
+    Ok(PgConfig::new_with_defaults(pg_config))
+}
+
+/// Run one step of building Postgres, appending its output to the build log in `pgdir`.
+///
+/// With `verbose_build` the output is also streamed to the terminal as it happens, prefixed with
+/// the Postgres version being built, since several versions may be building at once.
+fn run_build_command(
+    mut command: std::process::Command,
+    pg_config: &PgConfig,
+    pgdir: &PathBuf,
+    verbose_build: bool,
+) -> eyre::Result<()> {
+    let label = pg_config.label()?;
+    let log_path = get_pg_build_log(pgdir);
+    let log_file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log_path)
+        .wrap_err_with(|| format!("unable to open build log `{}`", log_path.display()))?;
+    let log_file = Arc::new(Mutex::new(log_file));
+
     let command_str = format!("{:?}", command);
     tracing::debug!(command = %command_str, "Running");
-    let child = command.spawn()?;
-    let output = child.wait_with_output()?;
-    tracing::trace!(status_code = %output.status, command = %command_str, "Finished");
+    writeln!(log_file.lock().unwrap(), "{}", command_str)?;
+
+    let mut child = command.spawn()?;
+    let stdout = child.stdout.take().expect("build command's stdout is not piped");
+    let stderr = child.stderr.take().expect("build command's stderr is not piped");
+
+    let collect_output = |reader: Box<dyn Read + Send>, to_stderr: bool| {
+        let log_file = log_file.clone();
+        let label = label.clone();
+        std::thread::spawn(move || {
+            let mut output = String::new();
+            for line in BufReader::new(reader).lines() {
+                let line = match line {
+                    Ok(line) => line,
+                    Err(_) => break,
+                };
+                if verbose_build {
+                    if to_stderr {
+                        eprintln!("{} {}", format!("[{}]", label).dimmed(), line);
+                    } else {
+                        println!("{} {}", format!("[{}]", label).dimmed(), line);
+                    }
+                }
+                writeln!(log_file.lock().unwrap(), "{}", line).ok();
+                output.push_str(&line);
+                output.push('\n');
+            }
+            output
+        })
+    };
+    let stdout = collect_output(Box::new(stdout), false);
+    let stderr = collect_output(Box::new(stderr), true);
 
-    if output.status.success() {
-        let mut pg_config = get_pg_installdir(pgdir);
-        pg_config.push("bin");
-        pg_config.push("pg_config");
-        Ok(PgConfig::new_with_defaults(pg_config))
+    let status = child.wait()?;
+    let stdout = stdout.join().expect("failed to collect build command's stdout");
+    let stderr = stderr.join().expect("failed to collect build command's stderr");
+    tracing::trace!(status_code = %status, command = %command_str, "Finished");
+
+    if status.success() {
+        Ok(())
+    } else if verbose_build {
+        // the output has already been shown
+        Err(eyre!("{}\nThe full build log is at {}", command_str, log_path.display()))
     } else {
         Err(eyre!(
-            "{}\n{}{}",
+            "{}\n{}{}\nThe full build log is at {}",
             command_str,
-            String::from_utf8(output.stdout).unwrap(),
-            String::from_utf8(output.stderr).unwrap()
+            stdout,
+            stderr,
+            log_path.display()
         ))
     }
 }
@@ -426,6 +591,12 @@ fn write_config(pg_configs: &Vec<PgConfig>, init: &Init) -> eyre::Result<()> {
     Ok(())
 }
 
+fn get_pg_build_log(pgdir: &PathBuf) -> PathBuf {
+    let mut log = PathBuf::from(pgdir);
+    log.push("pgx-build.log");
+    log
+}
+
 fn get_pg_installdir(pgdir: &PathBuf) -> PathBuf {
     let mut dir = PathBuf::from(pgdir);
     dir.push("pgx-install");
@@ -439,20 +610,47 @@ fn is_root_user() -> bool {
     }
 }
 
-pub(crate) fn initdb(bindir: &PathBuf, datadir: &PathBuf) -> eyre::Result<()> {
+pub(crate) fn initdb(
+    bindir: &Path,
+    datadir: &Path,
+    auth: AuthMethod,
+    password: Option<&str>,
+) -> eyre::Result<()> {
+    auth.check_password(password)?;
+
     println!(" {} data directory at {}", "Initializing".bold().green(), datadir.display());
     let mut command = std::process::Command::new(format!("{}/initdb", bindir.display()));
     command
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .args(C_LOCALE_FLAGS)
+        .arg(format!("--auth={}", auth.as_str()))
         .arg("-D")
-        .arg(&datadir);
+        .arg(datadir);
+
+    // initdb reads the superuser's password from a file, which must not outlive it
+    let pwfile = datadir.with_extension("pwfile");
+    if let Some(password) = password {
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&pwfile)
+            .wrap_err_with(|| format!("unable to create `{}`", pwfile.display()))?;
+        writeln!(file, "{}", password)?;
+        command.arg(format!("--pwfile={}", pwfile.display()));
+    }
 
     let command_str = format!("{:?}", command);
     tracing::debug!(command = %command_str, "Running");
 
-    let output = command.output().wrap_err_with(|| eyre!("unable to execute: {}", command_str))?;
+    let output = command.output();
+    if password.is_some() {
+        std::fs::remove_file(&pwfile)
+            .wrap_err_with(|| format!("unable to remove `{}`", pwfile.display()))?;
+    }
+    let output = output.wrap_err_with(|| eyre!("unable to execute: {}", command_str))?;
     tracing::trace!(command = %command_str, status_code = %output.status, "Finished");
 
     if !output.status.success() {
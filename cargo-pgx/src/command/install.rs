@@ -8,12 +8,13 @@ Use of this source code is governed by the MIT license that can be found in the
 */
 
 use crate::command::get::{find_control_file, get_property};
+use crate::command::start::start_postgres;
 use crate::profile::CargoProfile;
 use crate::CommandExecute;
 use cargo_toml::Manifest;
 use eyre::{eyre, WrapErr};
 use owo_colors::OwoColorize;
-use pgx_pg_config::{get_target_dir, PgConfig};
+use pgx_pg_config::{get_target_dir, PgConfig, Pgx};
 use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
@@ -40,15 +41,51 @@ pub(crate) struct Install {
     /// The `pg_config` path (default is first in $PATH)
     #[clap(long, short = 'c')]
     pg_config: Option<String>,
+    /// Use the `pg_config` that `cargo pgx init` configured for this Postgres version (`pg10`,
+    /// `pg11`, `pg12`, `pg13`, or `pg14`)
+    #[clap(long, conflicts_with = "pg_config")]
+    pg_version: Option<String>,
+    /// Skip building, and install the extension shared object already in the target directory
+    #[clap(long)]
+    skip_build: bool,
+    /// Install the extension's objects into this schema, by setting `schema` in its `.control` file
+    #[clap(long, value_parser = validate_extension_schema)]
+    extension_schema: Option<String>,
+    /// Also copy the generated extension schema (`$EXTNAME--$VERSION.sql`) into this directory
+    #[clap(long, value_parser)]
+    copy_schema_to: Option<PathBuf>,
+    /// Also build with the features listed in the `PGX_FEATURES` environment variable
+    #[clap(long)]
+    features_from_env: bool,
+    /// After installing, check that `CREATE EXTENSION` succeeds in a throwaway database on the
+    /// pgx-managed Postgres, which is started if need be
+    #[clap(long)]
+    verify: bool,
+    /// Like `--verify`, but against an already running Postgres listening on this port
+    #[clap(long)]
+    verify_port: Option<u16>,
+    /// Override whether the selected profile builds with `debug_assertions`
+    #[clap(long, value_enum)]
+    debug_assertions: Option<DebugAssertions>,
     #[clap(flatten)]
     features: clap_cargo::Features,
     #[clap(from_global, action = ArgAction::Count)]
     verbose: u8,
 }
 
+/// Whether to build with `debug_assertions`, regardless of the profile's setting
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum DebugAssertions {
+    On,
+    Off,
+}
+
 impl CommandExecute for Install {
     #[tracing::instrument(level = "error", skip(self))]
-    fn execute(self) -> eyre::Result<()> {
+    fn execute(mut self) -> eyre::Result<()> {
+        if self.features_from_env {
+            self.features = crate::manifest::features_from_env(self.features);
+        }
         let metadata = crate::metadata::metadata(&self.features, self.manifest_path.as_ref())
             .wrap_err("couldn't get cargo metadata")?;
         crate::metadata::validate(&metadata)?;
@@ -58,35 +95,141 @@ impl CommandExecute for Install {
         let package_manifest =
             Manifest::from_path(&package_manifest_path).wrap_err("Couldn't parse manifest")?;
 
-        let pg_config = match self.pg_config {
-            None => PgConfig::from_path(),
-            Some(config) => PgConfig::new_with_defaults(PathBuf::from(config)),
+        let pg_config = match (self.pg_config, self.pg_version) {
+            (Some(config), _) => PgConfig::new_with_defaults(PathBuf::from(config)),
+            (None, Some(pg_version)) => Pgx::from_config()?.get(&pg_version)?.clone(),
+            (None, None) => PgConfig::from_path(),
         };
         let pg_version = format!("pg{}", pg_config.major_version()?);
         let profile = CargoProfile::from_flags(self.release, self.profile.as_deref())?;
 
+        let debug_assertions = self.debug_assertions.map(|setting| setting == DebugAssertions::On);
+
         let features =
             crate::manifest::features_for_version(self.features, &package_manifest, &pg_version);
 
         install_extension(
             self.manifest_path.as_ref(),
             self.package.as_ref(),
-            package_manifest_path,
+            &package_manifest_path,
             &pg_config,
             &profile,
+            debug_assertions,
             self.test,
             None,
             &features,
-        )
+            self.skip_build,
+            self.extension_schema.as_deref(),
+        )?;
+
+        if let Some(copy_schema_to) = &self.copy_schema_to {
+            let extdir = make_relative(pg_config.extension_dir()?);
+            let installed = get_target_sql_file(&package_manifest_path, &extdir, &"/".into())?;
+            let filename = installed
+                .file_name()
+                .ok_or_else(|| eyre!("Could not get filename for `{}`", installed.display()))?;
+            copy_file(
+                &installed,
+                &copy_schema_to.join(filename),
+                "extension schema",
+                false,
+                &package_manifest_path,
+            )?;
+        }
+
+        if let Some(port) = self.verify_port {
+            verify_extension(&pg_config, port, &package_manifest_path)?;
+        } else if self.verify {
+            let pgx = Pgx::from_config()?;
+            let managed = pgx.get(&pg_version)?;
+            if managed.path() != pg_config.path() {
+                return Err(eyre!(
+                    "`--verify` needs a pgx-managed Postgres, but `{}` isn't one.  Use `--verify-port` to verify against a running Postgres",
+                    pg_config.path().unwrap_or_default().display()
+                ));
+            }
+            start_postgres(managed)?;
+            verify_extension(managed, managed.port()?, &package_manifest_path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Run `CREATE EXTENSION` in a throwaway database, on the Postgres listening on `port`, reporting
+/// the server's error if it fails
+#[tracing::instrument(level = "error", skip_all, fields(port))]
+fn verify_extension(
+    pg_config: &PgConfig,
+    port: u16,
+    package_manifest_path: impl AsRef<Path>,
+) -> eyre::Result<()> {
+    let (_, extname) = find_control_file(&package_manifest_path)?;
+    let dbname = format!("pgx_verify_{}", extname);
+
+    println!("{} extension {}", "   Verifying".bold().green(), extname.cyan());
+
+    let psql = |dbname: &str, sql: &str| -> eyre::Result<std::process::Output> {
+        let mut command = Command::new(pg_config.psql_path()?);
+        command
+            .env_remove("PGDATABASE")
+            .env_remove("PGHOST")
+            .env_remove("PGPORT")
+            .env_remove("PGUSER")
+            .env("PGPASSFILE", Pgx::passfile()?)
+            .arg("--no-psqlrc")
+            .arg("-v")
+            .arg("ON_ERROR_STOP=1")
+            .arg("-h")
+            .arg(pg_config.host())
+            .arg("-p")
+            .arg(port.to_string())
+            .arg("-d")
+            .arg(dbname)
+            .arg("-c")
+            .arg(sql);
+        tracing::debug!(command = ?command, "Running");
+        command.output().wrap_err_with(|| format!("failed to run {:?}", command))
+    };
+    let quoted_dbname = format!("\"{}\"", dbname);
+
+    let mut output = psql("template1", &format!("DROP DATABASE IF EXISTS {}", quoted_dbname))?;
+    if output.status.success() {
+        output = psql("template1", &format!("CREATE DATABASE {}", quoted_dbname))?;
     }
+    if !output.status.success() {
+        return Err(eyre!(
+            "couldn't create database `{}` to verify the extension in:\n\n{}",
+            dbname,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let created = psql(&dbname, &format!("CREATE EXTENSION \"{}\" CASCADE", extname))?;
+    // the database is only for this check, so drop it whatever the outcome
+    psql("template1", &format!("DROP DATABASE IF EXISTS {}", quoted_dbname))?;
+
+    if !created.status.success() {
+        return Err(eyre!(
+            "`CREATE EXTENSION {}` failed:\n\n{}",
+            extname,
+            String::from_utf8_lossy(&created.stderr)
+        ));
+    }
+
+    println!("{} extension {}", "    Verified".bold().green(), extname.cyan());
+    Ok(())
 }
 
 #[tracing::instrument(skip_all, fields(
     pg_version = %pg_config.version()?,
     profile = ?profile,
+    debug_assertions,
     test = is_test,
     base_directory = tracing::field::Empty,
     features = ?features.features,
+    skip_build,
+    extension_schema,
 ))]
 pub(crate) fn install_extension(
     user_manifest_path: Option<impl AsRef<Path>>,
@@ -94,13 +237,23 @@ pub(crate) fn install_extension(
     package_manifest_path: impl AsRef<Path>,
     pg_config: &PgConfig,
     profile: &CargoProfile,
+    debug_assertions: Option<bool>,
     is_test: bool,
     base_directory: Option<PathBuf>,
     features: &clap_cargo::Features,
+    skip_build: bool,
+    extension_schema: Option<&str>,
 ) -> eyre::Result<()> {
     let base_directory = base_directory.unwrap_or("/".into());
     tracing::Span::current()
         .record("base_directory", &tracing::field::display(&base_directory.display()));
+    let resolved_pg_config = pg_config.resolved_path()?;
+    let pg_version = pg_config.version()?;
+    tracing::debug!(
+        pg_config = %resolved_pg_config.display(),
+        version = %pg_version,
+        "Using pg_config"
+    );
 
     let manifest = Manifest::from_path(&package_manifest_path)?;
     let (control_file, extname) = find_control_file(&package_manifest_path)?;
@@ -114,18 +267,28 @@ pub(crate) fn install_extension(
 
     let versioned_so = get_property(&package_manifest_path, "module_pathname")?.is_none();
 
-    let build_command_output =
-        build_extension(user_manifest_path.as_ref(), user_package, &profile, &features)?;
-    let build_command_bytes = build_command_output.stdout;
-    let build_command_reader = BufReader::new(build_command_bytes.as_slice());
-    let build_command_stream = cargo_metadata::Message::parse_stream(build_command_reader);
-    let build_command_messages =
-        build_command_stream.collect::<Result<Vec<_>, std::io::Error>>()?;
+    let shlibpath = if skip_build {
+        find_built_library_file(&manifest, profile)?
+    } else {
+        let build_command_output = build_extension(
+            user_manifest_path.as_ref(),
+            user_package,
+            profile,
+            debug_assertions,
+            features,
+        )?;
+        let build_command_bytes = build_command_output.stdout;
+        let build_command_reader = BufReader::new(build_command_bytes.as_slice());
+        let build_command_stream = cargo_metadata::Message::parse_stream(build_command_reader);
+        let build_command_messages =
+            build_command_stream.collect::<Result<Vec<_>, std::io::Error>>()?;
+
+        find_library_file(&manifest, &build_command_messages)?
+    };
 
     println!("{} extension", "  Installing".bold().green(),);
     let pkgdir = make_relative(pg_config.pkglibdir()?);
     let extdir = make_relative(pg_config.extension_dir()?);
-    let shlibpath = find_library_file(&manifest, &build_command_messages)?;
 
     {
         let mut dest = base_directory.clone();
@@ -135,7 +298,7 @@ pub(crate) fn install_extension(
                 .file_name()
                 .ok_or_else(|| eyre!("Could not get filename for `{}`", control_file.display()))?,
         );
-        copy_file(&control_file, &dest, "control file", true, &package_manifest_path)?;
+        write_control_file(&package_manifest_path, &dest, extension_schema)?;
     }
 
     {
@@ -169,11 +332,13 @@ pub(crate) fn install_extension(
         &package_manifest_path,
         pg_config,
         profile,
+        debug_assertions,
         is_test,
         features,
         &extdir,
         &base_directory,
         true,
+        extension_schema,
     )?;
 
     println!("{} installing {}", "    Finished".bold().green(), extname);
@@ -217,6 +382,7 @@ pub(crate) fn build_extension(
     user_manifest_path: Option<impl AsRef<Path>>,
     user_package: Option<&String>,
     profile: &CargoProfile,
+    debug_assertions: Option<bool>,
     features: &clap_cargo::Features,
 ) -> eyre::Result<std::process::Output> {
     let flags = std::env::var("PGX_BUILD_FLAGS").unwrap_or_default();
@@ -234,6 +400,9 @@ pub(crate) fn build_extension(
         command.arg(user_package);
     }
     command.args(profile.cargo_args());
+    if let Some(debug_assertions) = debug_assertions {
+        command.env(profile.debug_assertions_env_var(), debug_assertions.to_string());
+    }
 
     let features_arg = features.features.join(" ");
     if !features_arg.trim().is_empty() {
@@ -290,11 +459,13 @@ fn copy_sql_files(
     package_manifest_path: impl AsRef<Path>,
     pg_config: &PgConfig,
     profile: &CargoProfile,
+    debug_assertions: Option<bool>,
     is_test: bool,
     features: &clap_cargo::Features,
     extdir: &PathBuf,
     base_directory: &PathBuf,
     skip_build: bool,
+    extension_schema: Option<&str>,
 ) -> eyre::Result<()> {
     let dest = get_target_sql_file(&package_manifest_path, extdir, base_directory)?;
     let (_, extname) = find_control_file(&package_manifest_path)?;
@@ -305,12 +476,21 @@ fn copy_sql_files(
         user_package,
         &package_manifest_path,
         profile,
+        debug_assertions,
         is_test,
         features,
         Some(&dest),
         Option::<String>::None,
         None,
         skip_build,
+        false,
+        extension_schema,
+        // the installed file is run by `CREATE EXTENSION`, which already runs it in a transaction
+        // and rejects scripts that contain `BEGIN`/`COMMIT`, so it's never wrapped in one
+        false,
+        false,
+        None,
+        None,
     )?;
 
     // now copy all the version upgrade files too
@@ -353,7 +533,8 @@ pub(crate) fn find_library_file(
     for message in build_command_messages {
         match message {
             cargo_metadata::Message::CompilerArtifact(artifact) => {
-                if artifact.target.name != *crate_name {
+                // cargo normalizes `-` to `_` in the name of a crate's library target
+                if artifact.target.name != crate_name.replace('-', "_") {
                     continue;
                 }
                 for filename in &artifact.filenames {
@@ -377,6 +558,39 @@ pub(crate) fn find_library_file(
     Ok(library_file_path)
 }
 
+/// Locate the extension's shared library from a previous build, in `get_target_dir()`, for
+/// when we've been asked to skip building it
+#[tracing::instrument(level = "error", skip_all)]
+pub(crate) fn find_built_library_file(
+    manifest: &cargo_toml::Manifest,
+    profile: &CargoProfile,
+) -> eyre::Result<PathBuf> {
+    let crate_name = if let Some(ref package) = manifest.package {
+        &package.name
+    } else {
+        return Err(eyre!("Could not get crate name from manifest."));
+    };
+
+    let so_extension = if cfg!(target_os = "macos") { "dylib" } else { "so" };
+    let mut library_file = get_target_dir()?;
+    library_file.push(profile.target_subdir());
+    library_file.push(format!("lib{}.{}", crate_name.replace('-', "_"), so_extension));
+
+    if !library_file.exists() {
+        return Err(eyre!(
+            "No shared library found at `{}`.  Build the extension first, or run without `--skip-build`.",
+            library_file.display()
+        ));
+    }
+
+    println!(
+        "{} build, using {}",
+        "    Skipping".bold().green(),
+        format_display_path(&library_file)?.cyan()
+    );
+    Ok(library_file)
+}
+
 pub(crate) fn get_version(manifest_path: impl AsRef<Path>) -> eyre::Result<String> {
     match get_property(&manifest_path, "default_version")? {
         Some(v) => {
@@ -431,6 +645,124 @@ pub(crate) fn format_display_path(path: impl AsRef<Path>) -> eyre::Result<String
     Ok(out)
 }
 
+/// The `.control` file properties which may be set from the `[package.metadata.pgx]` section of
+/// the crate's `Cargo.toml`
+const CONTROL_FILE_PROPERTIES: &[&str] = &[
+    "comment",
+    "default_version",
+    "directory",
+    "encoding",
+    "module_pathname",
+    "relocatable",
+    "requires",
+    "schema",
+    "superuser",
+    "trusted",
+];
+
+/// Render the extension's `.control` file exactly as it will be installed.
+///
+/// Properties from the `[package.metadata.pgx]` section of `Cargo.toml` take precedence over those
+/// in the crate's `.control` file, and placeholders such as `@CARGO_VERSION@` are substituted.
+/// An `extension_schema` takes precedence over both.
+pub(crate) fn render_control_file(
+    package_manifest_path: impl AsRef<Path>,
+    extension_schema: Option<&str>,
+) -> eyre::Result<String> {
+    let (control_file, _) = find_control_file(&package_manifest_path)?;
+    let mut contents = std::fs::read_to_string(&control_file)
+        .wrap_err_with(|| format!("failed to read `{}`", control_file.display()))?;
+
+    let manifest = Manifest::from_path(&package_manifest_path)?;
+    let metadata = manifest
+        .package
+        .and_then(|package| package.metadata)
+        .and_then(|metadata| metadata.get("pgx").cloned());
+    if let Some(metadata) = metadata {
+        let metadata = metadata
+            .as_table()
+            .ok_or_else(|| eyre!("`[package.metadata.pgx]` in Cargo.toml must be a table"))?;
+        for (key, value) in metadata {
+            if !CONTROL_FILE_PROPERTIES.contains(&key.as_str()) {
+                continue;
+            }
+            let value = control_file_value(key, value)?;
+            set_control_file_property(&mut contents, key, &value);
+        }
+    }
+    if let Some(extension_schema) = extension_schema {
+        set_control_file_property(&mut contents, "schema", &quote_control_value(extension_schema));
+    }
+
+    filter_contents(&package_manifest_path, contents)
+}
+
+fn control_file_value(key: &str, value: &cargo_toml::Value) -> eyre::Result<String> {
+    match value {
+        cargo_toml::Value::String(s) => Ok(quote_control_value(s)),
+        cargo_toml::Value::Boolean(b) => Ok(b.to_string()),
+        cargo_toml::Value::Array(values) => {
+            let values = values
+                .iter()
+                .map(|v| {
+                    v.as_str().ok_or_else(|| {
+                        eyre!("`{}` in `[package.metadata.pgx]` must be an array of strings", key)
+                    })
+                })
+                .collect::<eyre::Result<Vec<_>>>()?;
+            Ok(quote_control_value(&values.join(", ")))
+        }
+        _ => Err(eyre!("unsupported value for `{}` in `[package.metadata.pgx]`", key)),
+    }
+}
+
+/// Quote `value` as a control file string, doubling any `'` within it
+fn quote_control_value(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn set_control_file_property(contents: &mut String, key: &str, value: &str) {
+    let property = format!("{} = {}", key, value);
+    let mut found = false;
+    let mut lines = contents
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((k, _)) if k.trim() == key => {
+                found = true;
+                property.clone()
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>();
+    if !found {
+        lines.push(property);
+    }
+    *contents = lines.join("\n");
+    contents.push('\n');
+}
+
+pub(crate) fn write_control_file(
+    package_manifest_path: impl AsRef<Path>,
+    dest: &Path,
+    extension_schema: Option<&str>,
+) -> eyre::Result<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).wrap_err_with(|| {
+            format!("failed to create destination directory {}", parent.display())
+        })?;
+    }
+
+    println!(
+        "{} control file to {}",
+        "     Writing".bold().green(),
+        format_display_path(dest)?.cyan()
+    );
+
+    let contents = render_control_file(&package_manifest_path, extension_schema)?;
+    std::fs::write(dest, contents)
+        .wrap_err_with(|| format!("failed writing control file to `{}`", dest.display()))
+}
+
 fn filter_contents(manifest_path: impl AsRef<Path>, mut input: String) -> eyre::Result<String> {
     if input.contains("@GIT_HASH@") {
         // avoid doing this if we don't actually have the token
@@ -443,3 +775,42 @@ fn filter_contents(manifest_path: impl AsRef<Path>, mut input: String) -> eyre::
 
     Ok(input)
 }
+
+/// The schema must be usable as a single-quoted `.control` file value, and an unquoted SQL name
+pub(crate) fn validate_extension_schema(schema: &str) -> eyre::Result<String> {
+    let mut chars = schema.chars();
+    let valid = matches!(chars.next(), Some(c) if c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '$');
+    if !valid || schema.len() > 63 {
+        return Err(eyre!(
+            "`{}` is not a valid schema name.  It must be at most 63 characters of lowercase letters, digits, `_` and `$`, and not start with a digit or `$`",
+            schema
+        ));
+    }
+    if schema.starts_with("pg_") {
+        return Err(eyre!(
+            "`{}` is not a valid schema name, as `pg_` is reserved by Postgres",
+            schema
+        ));
+    }
+    Ok(schema.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_file_values_escape_quotes() {
+        let comment = cargo_toml::Value::String("Bob's extension".to_string());
+        assert_eq!(control_file_value("comment", &comment).unwrap(), "'Bob''s extension'");
+
+        let requires = cargo_toml::Value::Array(vec![
+            cargo_toml::Value::String("o'clock".to_string()),
+            cargo_toml::Value::String("plpgsql".to_string()),
+        ]);
+        assert_eq!(control_file_value("requires", &requires).unwrap(), "'o''clock, plpgsql'");
+
+        assert_eq!(quote_control_value("bob's"), "'bob''s'");
+    }
+}
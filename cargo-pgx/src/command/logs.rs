@@ -0,0 +1,106 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::CommandExecute;
+use cargo_toml::Manifest;
+use eyre::{eyre, WrapErr};
+use owo_colors::OwoColorize;
+use pgx_pg_config::{PgConfig, Pgx};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// How often `--follow` checks the log for more output
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Print the server log of a pgx-managed Postgres instance
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct Logs {
+    /// The Postgres version whose log to print (`pg10`, `pg11`, `pg12`, `pg13`, or `pg14`)
+    #[clap(env = "PG_VERSION")]
+    pg_version: Option<String>,
+    /// Keep printing whatever is appended to the log, like `tail -f`
+    #[clap(long, short)]
+    follow: bool,
+    /// Package to determine default `pg_version` with (see `cargo help pkgid`)
+    #[clap(long, short)]
+    package: Option<String>,
+    /// Path to Cargo.toml
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+impl CommandExecute for Logs {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let pgx = Pgx::from_config()?;
+
+        let pg_version = match self.pg_version {
+            Some(s) => s,
+            None => {
+                let metadata =
+                    crate::metadata::metadata(&Default::default(), self.manifest_path.as_ref())
+                        .wrap_err("couldn't get cargo metadata")?;
+                crate::metadata::validate(&metadata)?;
+                let package_manifest_path =
+                    crate::manifest::manifest_path(&metadata, self.package.as_ref())
+                        .wrap_err("Couldn't get manifest path")?;
+                let package_manifest = Manifest::from_path(&package_manifest_path)
+                    .wrap_err("Couldn't parse manifest")?;
+
+                crate::manifest::default_pg_version(&package_manifest)
+                    .ok_or(eyre!("no provided `pg$VERSION` flag."))?
+            }
+        };
+
+        print_log(pgx.get(&pg_version)?, self.follow)
+    }
+}
+
+#[tracing::instrument(level = "error", skip_all, fields(pg_version = %pg_config.version()?))]
+pub(crate) fn print_log(pg_config: &PgConfig, follow: bool) -> eyre::Result<()> {
+    let path = pg_config.log_file()?;
+    let mut file = match File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!(
+                "{} no log at `{}` yet, it's created the first time `cargo pgx start` starts Postgres v{}",
+                "    Skipping".bold().yellow(),
+                path.display(),
+                pg_config.major_version()?
+            );
+            return Ok(());
+        }
+        Err(e) => return Err(e).wrap_err_with(|| format!("failed to open `{}`", path.display())),
+    };
+
+    let mut stdout = std::io::stdout();
+    let mut position = std::io::copy(&mut file, &mut stdout)?;
+    stdout.flush()?;
+
+    if !follow {
+        return Ok(());
+    }
+
+    loop {
+        std::thread::sleep(FOLLOW_INTERVAL);
+
+        let len = file.metadata()?.len();
+        if len < position {
+            // the log was truncated, so start over from its beginning
+            position = file.seek(SeekFrom::Start(0))?;
+        }
+        position += std::io::copy(&mut (&mut file).take(len - position), &mut stdout)?;
+        stdout.flush()?;
+    }
+}
@@ -0,0 +1,227 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::command::get::find_control_file;
+use crate::command::install::get_version;
+use crate::CommandExecute;
+use eyre::{eyre, WrapErr};
+use owo_colors::OwoColorize;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Create and check the extension's versioned SQL upgrade scripts
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct Migrate {
+    #[clap(subcommand)]
+    subcommand: MigrateSubCommands,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+impl CommandExecute for Migrate {
+    fn execute(self) -> eyre::Result<()> {
+        match self.subcommand {
+            MigrateSubCommands::New(c) => c.execute(),
+            MigrateSubCommands::Check(c) => c.execute(),
+        }
+    }
+}
+
+#[derive(clap::Subcommand, Debug)]
+enum MigrateSubCommands {
+    New(MigrateNew),
+    Check(MigrateCheck),
+}
+
+/// Create an empty `sql/$EXTNAME--$FROM--$TO.sql` upgrade script
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct MigrateNew {
+    /// The extension version the script upgrades from
+    from: String,
+    /// The extension version the script upgrades to
+    to: String,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+    /// Package to create the upgrade script for (see `cargo help pkgid`)
+    #[clap(long, short)]
+    package: Option<String>,
+    /// Path to Cargo.toml
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+}
+
+impl CommandExecute for MigrateNew {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let package_manifest_path =
+            package_manifest_path(self.manifest_path.as_ref(), self.package.as_ref())?;
+        let (_, extname) = find_control_file(&package_manifest_path)?;
+
+        if self.from == self.to {
+            return Err(eyre!("cannot upgrade version `{}` to itself", self.from));
+        }
+        for version in [&self.from, &self.to] {
+            if version.is_empty() || version.contains("--") || version.contains('/') {
+                return Err(eyre!("`{}` is not a valid extension version", version));
+            }
+        }
+
+        let sql_dir = sql_dir(&package_manifest_path)?;
+        std::fs::create_dir_all(&sql_dir)
+            .wrap_err_with(|| format!("unable to create `{}`", sql_dir.display()))?;
+
+        let filename = sql_dir.join(format!("{}--{}--{}.sql", extname, self.from, self.to));
+        if filename.exists() {
+            return Err(eyre!("`{}` already exists", filename.display()));
+        }
+
+        std::fs::write(
+            &filename,
+            format!(
+                "-- Upgrades extension `{}` from version {} to {}\n\
+                 -- Run by `ALTER EXTENSION {} UPDATE TO '{}';`\n\n",
+                extname, self.from, self.to, extname, self.to
+            ),
+        )
+        .wrap_err_with(|| format!("unable to write `{}`", filename.display()))?;
+
+        println!("{} upgrade script {}", "     Created".bold().green(), filename.display().cyan());
+        Ok(())
+    }
+}
+
+/// Check that every shipped version can be upgraded to the control file's `default_version`
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct MigrateCheck {
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+    /// Package to check the upgrade scripts of (see `cargo help pkgid`)
+    #[clap(long, short)]
+    package: Option<String>,
+    /// Path to Cargo.toml
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+}
+
+impl CommandExecute for MigrateCheck {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let package_manifest_path =
+            package_manifest_path(self.manifest_path.as_ref(), self.package.as_ref())?;
+        let (_, extname) = find_control_file(&package_manifest_path)?;
+        let default_version = get_version(&package_manifest_path)?;
+
+        // every upgrade script is an edge from one version to another
+        let mut upgrades = BTreeMap::<String, Vec<String>>::new();
+        let mut versions = BTreeSet::new();
+        versions.insert(default_version.clone());
+
+        let sql_dir = sql_dir(&package_manifest_path)?;
+        if let Ok(dir) = std::fs::read_dir(&sql_dir) {
+            for entry in dir {
+                let filename = entry?.file_name().to_string_lossy().to_string();
+                let versions_part = match filename
+                    .strip_prefix(&format!("{}--", extname))
+                    .and_then(|rest| rest.strip_suffix(".sql"))
+                {
+                    Some(versions_part) => versions_part,
+                    None => continue,
+                };
+
+                match versions_part.split_once("--") {
+                    Some((from, to)) => {
+                        upgrades.entry(from.to_string()).or_default().push(to.to_string());
+                        versions.insert(from.to_string());
+                        versions.insert(to.to_string());
+                    }
+                    // a full install script for an older version
+                    None => {
+                        versions.insert(versions_part.to_string());
+                    }
+                }
+            }
+        }
+
+        if versions.len() == 1 {
+            println!(
+                "{} no upgrade scripts in {}, nothing to check",
+                "    Finished".bold().green(),
+                sql_dir.display()
+            );
+            return Ok(());
+        }
+
+        let mut versions = versions.into_iter().collect::<Vec<_>>();
+        versions.sort_by_cached_key(|v| version_key(v));
+
+        let unreachable = versions
+            .iter()
+            .filter(|version| !has_upgrade_path(&upgrades, version, &default_version))
+            .collect::<Vec<_>>();
+
+        if !unreachable.is_empty() {
+            return Err(eyre!(
+                "no upgrade path to default_version `{}` from version(s) {} (lowest shipped version is `{}`)",
+                default_version,
+                unreachable.iter().map(|v| format!("`{}`", v)).collect::<Vec<_>>().join(", "),
+                versions[0],
+            ));
+        }
+
+        println!(
+            "{} every version from {} upgrades to {}",
+            "    Finished".bold().green(),
+            versions[0].cyan(),
+            default_version.cyan()
+        );
+        Ok(())
+    }
+}
+
+fn package_manifest_path(
+    manifest_path: Option<&PathBuf>,
+    package: Option<&String>,
+) -> eyre::Result<PathBuf> {
+    let metadata = crate::metadata::metadata(&Default::default(), manifest_path)
+        .wrap_err("couldn't get cargo metadata")?;
+    crate::metadata::validate(&metadata)?;
+    crate::manifest::manifest_path(&metadata, package).wrap_err("Couldn't get manifest path")
+}
+
+fn sql_dir(package_manifest_path: &Path) -> eyre::Result<PathBuf> {
+    let parent = package_manifest_path
+        .parent()
+        .ok_or_else(|| eyre!("could not get parent of `{}`", package_manifest_path.display()))?;
+    Ok(parent.join("sql"))
+}
+
+/// Order versions such as `1.2.10` after `1.2.9`, by comparing their numeric parts as numbers
+fn version_key(version: &str) -> Vec<Result<u64, String>> {
+    version.split('.').map(|part| part.parse::<u64>().map_err(|_| part.to_string())).collect()
+}
+
+/// Can Postgres chain upgrade scripts to get from `from` to `to`?
+fn has_upgrade_path(upgrades: &BTreeMap<String, Vec<String>>, from: &str, to: &str) -> bool {
+    let mut seen = BTreeSet::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(version) = queue.pop_front() {
+        if version == to {
+            return true;
+        }
+        if seen.insert(version) {
+            for next in upgrades.get(version).into_iter().flatten() {
+                queue.push_back(next);
+            }
+        }
+    }
+    false
+}
@@ -7,17 +7,24 @@ All rights reserved.
 Use of this source code is governed by the MIT license that can be found in the LICENSE file.
 */
 
+pub(crate) mod completions;
 pub(crate) mod connect;
+pub(crate) mod doctor;
+pub(crate) mod env;
 pub(crate) mod get;
 pub(crate) mod init;
 pub(crate) mod install;
+pub(crate) mod logs;
+pub(crate) mod migrate;
 pub(crate) mod new;
 pub(crate) mod package;
 pub(crate) mod pgx;
+pub(crate) mod psql_file;
 pub(crate) mod run;
 pub(crate) mod schema;
 pub(crate) mod start;
 pub(crate) mod status;
 pub(crate) mod stop;
 pub(crate) mod test;
+pub(crate) mod uninstall;
 pub(crate) mod version;
@@ -9,7 +9,7 @@ Use of this source code is governed by the MIT license that can be found in the
 
 use eyre::eyre;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use crate::CommandExecute;
@@ -20,9 +20,15 @@ use crate::CommandExecute;
 pub(crate) struct New {
     /// The name of the extension
     name: String,
+    /// The name of the Cargo crate, if it should differ from the extension name
+    #[clap(long, alias = "lib-name")]
+    crate_name: Option<String>,
     /// Create a background worker template
     #[clap(long, short)]
     bgworker: bool,
+    /// Create a minimal template, without tests and with only the Postgres version features of pgx
+    #[clap(long, conflicts_with = "bgworker")]
+    minimal: bool,
     #[clap(from_global, action = ArgAction::Count)]
     verbose: u8,
 }
@@ -31,31 +37,172 @@ impl CommandExecute for New {
     #[tracing::instrument(level = "error", skip(self))]
     fn execute(self) -> eyre::Result<()> {
         validate_extension_name(&self.name)?;
-        let path = PathBuf::from_str(&format!("{}/", self.name)).unwrap();
-        create_crate_template(path, &self.name, self.bgworker)
+        let crate_name = self.crate_name.as_deref().unwrap_or(&self.name);
+        validate_crate_name(crate_name)?;
+        let path = PathBuf::from_str(&format!("{}/", crate_name)).unwrap();
+        create_crate_template(path, &self.name, crate_name, self.bgworker, self.minimal)
     }
 }
 
+/// Postgres keywords which can't be used as an unquoted extension name: the fully reserved ones,
+/// and those which can only be function or type names
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "binary",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "similar",
+    "some",
+    "symmetric",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+/// The extension name is used as an unquoted SQL identifier, so must be one
 fn validate_extension_name(extname: &str) -> eyre::Result<()> {
-    for c in extname.chars() {
-        if !c.is_alphanumeric() && c != '_' && !c.is_lowercase() {
+    let mut chars = extname.chars();
+    match chars.next() {
+        None => return Err(eyre!("Extension name must not be empty")),
+        Some(c) if !c.is_ascii_lowercase() => {
+            return Err(eyre!("Extension name must start with a lowercase ASCII letter"))
+        }
+        _ => (),
+    }
+    for c in chars {
+        if !c.is_ascii_lowercase() && !c.is_ascii_digit() && c != '_' {
             return Err(eyre!("Extension name must be in the set of [a-z0-9_]"));
         }
     }
+    if RESERVED_KEYWORDS.contains(&extname) {
+        return Err(eyre!(
+            "Extension name `{}` is a reserved SQL keyword, and would always need quoting",
+            extname
+        ));
+    }
+    Ok(())
+}
+
+fn validate_crate_name(crate_name: &str) -> eyre::Result<()> {
+    let mut chars = crate_name.chars();
+    match chars.next() {
+        None => return Err(eyre!("Crate name must not be empty")),
+        Some(c) if !c.is_ascii_alphabetic() && c != '_' => {
+            return Err(eyre!("Crate name must start with an ASCII letter or `_`"))
+        }
+        _ => (),
+    }
+    for c in chars {
+        if !c.is_ascii_alphanumeric() && c != '_' && c != '-' {
+            return Err(eyre!("Crate name must be in the set of [a-zA-Z0-9_-]"));
+        }
+    }
     Ok(())
 }
 
-#[tracing::instrument(skip_all, fields(path, name))]
+#[tracing::instrument(skip_all, fields(path, name, crate_name))]
 pub(crate) fn create_crate_template(
     path: PathBuf,
     name: &str,
+    crate_name: &str,
     is_bgworker: bool,
+    is_minimal: bool,
 ) -> eyre::Result<()> {
     create_directory_structure(&path)?;
     create_control_file(&path, name)?;
-    create_cargo_toml(&path, name)?;
+    create_cargo_toml(&path, crate_name, is_minimal)?;
     create_dotcargo_config_toml(&path, name)?;
-    create_lib_rs(&path, name, is_bgworker)?;
+    create_lib_rs(&path, name, is_bgworker, is_minimal)?;
     create_git_ignore(&path, name)?;
 
     Ok(())
@@ -87,13 +234,19 @@ fn create_control_file(path: &PathBuf, name: &str) -> Result<(), std::io::Error>
     Ok(())
 }
 
-fn create_cargo_toml(path: &PathBuf, name: &str) -> Result<(), std::io::Error> {
-    let mut filename = path.clone();
+fn create_cargo_toml(path: &Path, name: &str, is_minimal: bool) -> Result<(), std::io::Error> {
+    let mut filename = path.to_path_buf();
 
     filename.push("Cargo.toml");
     let mut file = std::fs::File::create(filename)?;
 
-    file.write_all(&format!(include_str!("../templates/cargo_toml"), name = name).as_bytes())?;
+    if is_minimal {
+        file.write_all(
+            format!(include_str!("../templates/cargo_toml_minimal"), name = name).as_bytes(),
+        )?;
+    } else {
+        file.write_all(format!(include_str!("../templates/cargo_toml"), name = name).as_bytes())?;
+    }
 
     Ok(())
 }
@@ -110,8 +263,13 @@ fn create_dotcargo_config_toml(path: &PathBuf, _name: &str) -> Result<(), std::i
     Ok(())
 }
 
-fn create_lib_rs(path: &PathBuf, name: &str, is_bgworker: bool) -> Result<(), std::io::Error> {
-    let mut filename = path.clone();
+fn create_lib_rs(
+    path: &Path,
+    name: &str,
+    is_bgworker: bool,
+    is_minimal: bool,
+) -> Result<(), std::io::Error> {
+    let mut filename = path.to_path_buf();
 
     filename.push("src");
     filename.push("lib.rs");
@@ -121,6 +279,10 @@ fn create_lib_rs(path: &PathBuf, name: &str, is_bgworker: bool) -> Result<(), st
         file.write_all(
             &format!(include_str!("../templates/bgworker_lib_rs"), name = name).as_bytes(),
         )?;
+    } else if is_minimal {
+        file.write_all(
+            format!(include_str!("../templates/lib_rs_minimal"), name = name).as_bytes(),
+        )?;
     } else {
         file.write_all(&format!(include_str!("../templates/lib_rs"), name = name).as_bytes())?;
     }
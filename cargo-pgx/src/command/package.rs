@@ -110,9 +110,12 @@ pub(crate) fn package_extension(
         &package_manifest_path,
         pg_config,
         profile,
+        None,
         is_test,
         Some(out_dir),
         features,
+        false,
+        None,
     )
 }
 
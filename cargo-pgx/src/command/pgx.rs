@@ -31,14 +31,21 @@ enum CargoPgxSubCommands {
     Start(super::start::Start),
     Stop(super::stop::Stop),
     Status(super::status::Status),
+    Logs(super::logs::Logs),
     New(super::new::New),
     Install(super::install::Install),
+    Uninstall(super::uninstall::Uninstall),
     Package(super::package::Package),
     Schema(super::schema::Schema),
     Run(super::run::Run),
     Connect(super::connect::Connect),
+    PsqlFile(super::psql_file::PsqlFile),
+    Migrate(super::migrate::Migrate),
     Test(super::test::Test),
     Get(super::get::Get),
+    Env(super::env::Env),
+    Doctor(super::doctor::Doctor),
+    Completions(super::completions::Completions),
 }
 
 impl CommandExecute for CargoPgxSubCommands {
@@ -50,14 +57,21 @@ impl CommandExecute for CargoPgxSubCommands {
             Start(c) => c.execute(),
             Stop(c) => c.execute(),
             Status(c) => c.execute(),
+            Logs(c) => c.execute(),
             New(c) => c.execute(),
             Install(c) => c.execute(),
+            Uninstall(c) => c.execute(),
             Package(c) => c.execute(),
             Schema(c) => c.execute(),
             Run(c) => c.execute(),
             Connect(c) => c.execute(),
+            PsqlFile(c) => c.execute(),
+            Migrate(c) => c.execute(),
             Test(c) => c.execute(),
             Get(c) => c.execute(),
+            Env(c) => c.execute(),
+            Doctor(c) => c.execute(),
+            Completions(c) => c.execute(),
         }
     }
 }
@@ -0,0 +1,154 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::command::get::get_property;
+use crate::command::start::start_postgres;
+use crate::CommandExecute;
+use eyre::{eyre, WrapErr};
+use owo_colors::OwoColorize;
+use pgx_pg_config::{createdb, PgConfig, Pgx};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Run a `.sql` file, via psql, against a pgx-managed Postgres instance
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct PsqlFile {
+    /// Do you want to run against Postgres `pg10`, `pg11`, `pg12`, `pg13`, `pg14`?
+    #[clap(env = "PG_VERSION")]
+    pg_version: String,
+    /// The `.sql` file to run
+    #[clap(value_parser)]
+    file: PathBuf,
+    /// The database to run the file in (and create if the first time).  Defaults to a database with the same name as the current extension name
+    #[clap(env = "DBNAME")]
+    dbname: Option<String>,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+    /// Package to determine default `dbname` with (see `cargo help pkgid`)
+    #[clap(long, short)]
+    package: Option<String>,
+    /// Path to Cargo.toml
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+    /// Stop at the first statement that fails, rather than running the rest of the file
+    #[clap(long)]
+    stop_on_error: bool,
+}
+
+impl CommandExecute for PsqlFile {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let pgx = Pgx::from_config()?;
+        let pg_config = pgx.get(&self.pg_version)?;
+
+        let dbname = match self.dbname {
+            Some(dbname) => dbname,
+            None => {
+                // We should infer from package
+                let metadata =
+                    crate::metadata::metadata(&Default::default(), self.manifest_path.as_ref())
+                        .wrap_err("couldn't get cargo metadata")?;
+                crate::metadata::validate(&metadata)?;
+                let package_manifest_path =
+                    crate::manifest::manifest_path(&metadata, self.package.as_ref())
+                        .wrap_err("Couldn't get manifest path")?;
+
+                get_property(&package_manifest_path, "extname")
+                    .wrap_err("could not determine extension name")?
+                    .ok_or(eyre!("extname not found in control file"))?
+            }
+        };
+
+        run_psql_file(pg_config, &dbname, &self.file, self.stop_on_error)
+    }
+}
+
+#[tracing::instrument(level = "error", skip_all, fields(
+    pg_version = %pg_config.version()?,
+    dbname,
+    file = %file.as_ref().display(),
+))]
+pub(crate) fn run_psql_file(
+    pg_config: &PgConfig,
+    dbname: &str,
+    file: impl AsRef<Path>,
+    stop_on_error: bool,
+) -> eyre::Result<()> {
+    let file = file.as_ref();
+    if !file.is_file() {
+        return Err(eyre!("`{}` does not exist or is not a file", file.display()));
+    }
+
+    start_postgres(pg_config)?;
+
+    if !createdb(pg_config, dbname, false, true)? {
+        println!("{} existing database {}", "    Re-using".bold().cyan(), dbname);
+    }
+
+    println!("{} {} in database {}", "     Running".bold().green(), file.display().cyan(), dbname);
+
+    let mut command = Command::new(pg_config.psql_path()?);
+    command
+        .env_remove("PGDATABASE")
+        .env_remove("PGHOST")
+        .env_remove("PGPORT")
+        .env_remove("PGUSER")
+        .env("PGPASSFILE", Pgx::passfile()?)
+        // psql prints each statement's command tag (or its results) to stdout, and reports
+        // each failed statement, along with its line number, on stderr
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::piped())
+        .arg("--no-psqlrc")
+        .arg("-v")
+        .arg(format!("ON_ERROR_STOP={}", if stop_on_error { 1 } else { 0 }))
+        .arg("-h")
+        .arg(pg_config.host())
+        .arg("-p")
+        .arg(pg_config.port()?.to_string())
+        .arg("-f")
+        .arg(file)
+        .arg(dbname);
+
+    let command_str = format!("{:?}", command);
+    tracing::debug!(command = %command_str, "Running");
+    let mut child =
+        command.spawn().wrap_err_with(|| format!("failed to spawn psql: {}", command_str))?;
+
+    let mut num_errors = 0_usize;
+    let stderr = child.stderr.take().expect("psql stderr was not captured");
+    for line in BufReader::new(stderr).lines() {
+        let line = line.wrap_err("failed to read psql output")?;
+        if line.contains("ERROR:") || line.contains("FATAL:") {
+            num_errors += 1;
+            eprintln!("{}", line.red());
+        } else {
+            eprintln!("{}", line);
+        }
+    }
+
+    let status =
+        child.wait().wrap_err_with(|| format!("failed waiting for psql: {}", command_str))?;
+    tracing::trace!(status_code = %status, command = %command_str, "Finished");
+
+    if num_errors > 0 {
+        return Err(eyre!(
+            "{} statement(s) in `{}` failed{}",
+            num_errors,
+            file.display(),
+            if stop_on_error { ", stopped at the first failure" } else { "" }
+        ));
+    } else if !status.success() {
+        return Err(eyre!("psql exited with {} running `{}`", status, file.display()));
+    }
+
+    println!("{} running {}", "    Finished".bold().green(), file.display().cyan());
+    Ok(())
+}
@@ -137,9 +137,12 @@ pub(crate) fn run(
         package_manifest_path,
         pg_config,
         profile,
+        None,
         false,
         None,
         features,
+        false,
+        None,
     )?;
 
     // restart postgres
@@ -164,6 +167,7 @@ pub(crate) fn exec_psql(pg_config: &PgConfig, dbname: &str, pgcli: bool) -> eyre
         .env_remove("PGHOST")
         .env_remove("PGPORT")
         .env_remove("PGUSER")
+        .env("PGPASSFILE", Pgx::passfile()?)
         .arg("-h")
         .arg(pg_config.host())
         .arg("-p")
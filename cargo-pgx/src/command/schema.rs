@@ -7,7 +7,7 @@ All rights reserved.
 Use of this source code is governed by the MIT license that can be found in the LICENSE file.
 */
 use crate::command::get::{find_control_file, get_property};
-use crate::command::install::format_display_path;
+use crate::command::install::{format_display_path, validate_extension_schema, write_control_file};
 use crate::pgx_pg_sys_stub::PgxPgSysStub;
 use crate::profile::CargoProfile;
 use crate::CommandExecute;
@@ -17,7 +17,7 @@ use object::Object;
 use once_cell::sync::OnceCell;
 use owo_colors::OwoColorize;
 use pgx_pg_config::{get_target_dir, PgConfig, Pgx};
-use pgx_utils::sql_entity_graph::{ControlFile, PgxSql, SqlGraphEntity};
+use pgx_utils::sql_entity_graph::{wrap_in_transaction, ControlFile, PgxSql, SqlGraphEntity};
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
@@ -66,11 +66,33 @@ pub(crate) struct Schema {
     /// A path to output a produced GraphViz DOT file
     #[clap(long, short, value_parser)]
     dot: Option<PathBuf>,
+    /// A path to output the extension's `.control` file, as it would be installed
+    #[clap(long, value_parser)]
+    emit_control: Option<PathBuf>,
     #[clap(from_global, action = ArgAction::Count)]
     verbose: u8,
     /// Skip building a fresh extension shared object.
     #[clap(long)]
     skip_build: bool,
+    /// Rather than stopping at the first SQL entity which fails to translate, report all of them
+    #[clap(long)]
+    keep_going: bool,
+    /// Generate the schema as if the extension's `.control` file set `schema` to this
+    #[clap(long, value_parser = validate_extension_schema)]
+    extension_schema: Option<String>,
+    /// Wrap the SQL in `BEGIN;`/`COMMIT;`, for running it outside of `CREATE EXTENSION`
+    #[clap(long)]
+    transaction_wrap: bool,
+    /// Don't qualify objects outside `#[pg_schema]` modules with the extension's schema, leaving
+    /// them to whichever schema is first in the `search_path`
+    #[clap(long)]
+    no_default_schema: bool,
+    /// Prepend the contents of this file to the generated SQL
+    #[clap(long, value_parser)]
+    include_header: Option<PathBuf>,
+    /// Append the contents of this file to the generated SQL
+    #[clap(long, value_parser)]
+    include_footer: Option<PathBuf>,
 }
 
 impl CommandExecute for Schema {
@@ -120,18 +142,41 @@ impl CommandExecute for Schema {
 
         let profile = CargoProfile::from_flags(self.release, self.profile.as_deref())?;
 
+        // read these up front, so a typo'd path fails before the (slow) build
+        let read = |path: &PathBuf| {
+            std::fs::read_to_string(path)
+                .wrap_err_with(|| format!("couldn't read `{}`", path.display()))
+        };
+        let header = self.include_header.as_ref().map(read).transpose()?;
+        let footer = self.include_footer.as_ref().map(read).transpose()?;
+
+        if let Some(emit_control) = &self.emit_control {
+            write_control_file(
+                &package_manifest_path,
+                emit_control,
+                self.extension_schema.as_deref(),
+            )?;
+        }
+
         generate_schema(
             &pg_config,
             self.manifest_path.as_ref(),
             self.package.as_ref(),
             package_manifest_path,
             &profile,
+            None,
             self.test,
             &features,
             self.out.as_ref(),
             self.dot,
             log_level,
             self.skip_build,
+            self.keep_going,
+            self.extension_schema.as_deref(),
+            self.transaction_wrap,
+            self.no_default_schema,
+            header.as_deref(),
+            footer.as_deref(),
         )
     }
 }
@@ -139,6 +184,7 @@ impl CommandExecute for Schema {
 #[tracing::instrument(level = "error", skip_all, fields(
     pg_version = %pg_config.version()?,
     profile = ?profile,
+    debug_assertions,
     test = is_test,
     path = path.as_ref().map(|path| tracing::field::display(path.as_ref().display())),
     dot,
@@ -150,15 +196,22 @@ pub(crate) fn generate_schema(
     user_package: Option<&String>,
     package_manifest_path: impl AsRef<Path>,
     profile: &CargoProfile,
+    debug_assertions: Option<bool>,
     is_test: bool,
     features: &clap_cargo::Features,
     path: Option<impl AsRef<std::path::Path>>,
     dot: Option<impl AsRef<std::path::Path>>,
     log_level: Option<String>,
     skip_build: bool,
+    keep_going: bool,
+    extension_schema: Option<&str>,
+    transaction_wrap: bool,
+    no_default_schema: bool,
+    header: Option<&str>,
+    footer: Option<&str>,
 ) -> eyre::Result<()> {
     let manifest = Manifest::from_path(&package_manifest_path)?;
-    let (control_file, _extname) = find_control_file(&package_manifest_path)?;
+    let (control_file, extname) = find_control_file(&package_manifest_path)?;
     let package_name = &manifest
         .package
         .as_ref()
@@ -202,6 +255,9 @@ pub(crate) fn generate_schema(
         }
 
         command.args(profile.cargo_args());
+        if let Some(debug_assertions) = debug_assertions {
+            command.env(profile.debug_assertions_env_var(), debug_assertions.to_string());
+        }
 
         if let Some(log_level) = &log_level {
             command.env("RUST_LOG", log_level);
@@ -351,6 +407,7 @@ pub(crate) fn generate_schema(
 
     tracing::debug!("Collecting {} SQL entities", fns_to_call.len());
     let mut entities = Vec::default();
+    let mut entity_errors = Vec::default();
     let sql_mapping;
 
     #[rustfmt::skip] // explict extern "Rust" is more clear here
@@ -387,23 +444,69 @@ pub(crate) fn generate_schema(
         > = lib
             .get("__pgx_marker".as_bytes())
             .expect(&format!("Couldn't call __pgx_marker"));
-        let control_file_entity = SqlGraphEntity::ExtensionRoot(
-            symbol().expect("Failed to get control file information"),
-        );
+        let mut control_file = symbol().expect("Failed to get control file information");
+        // objects are qualified with the schema the extension will be installed into
+        if let Some(extension_schema) = extension_schema {
+            control_file.schema = Some(extension_schema.to_string());
+        }
+        let control_file_entity = SqlGraphEntity::ExtensionRoot(control_file);
         entities.push(control_file_entity);
 
         for symbol_to_call in fns_to_call {
             let symbol: libloading::os::unix::Symbol<unsafe extern "Rust" fn() -> SqlGraphEntity> =
                 lib.get(symbol_to_call.as_bytes())
                     .expect(&format!("Couldn't call {:#?}", symbol_to_call));
-            let entity = symbol();
-            entities.push(entity);
+            if keep_going {
+                match std::panic::catch_unwind(|| symbol()) {
+                    Ok(entity) => entities.push(entity),
+                    Err(panic) => entity_errors.push(format!(
+                        "{}: {}",
+                        symbol_to_call,
+                        panic_message(&panic)
+                    )),
+                }
+            } else {
+                let entity = symbol();
+                entities.push(entity);
+            }
         }
     };
 
-    let pgx_sql =
-        PgxSql::build(sql_mapping, entities.into_iter(), package_name.to_string(), versioned_so)
-            .wrap_err("SQL generation error")?;
+    let mut pgx_sql = PgxSql::build(sql_mapping, entities.into_iter(), extname, versioned_so)
+        .wrap_err("SQL generation error")?;
+    pgx_sql.no_default_schema = no_default_schema;
+
+    if keep_going {
+        let (_, sql_errors) = pgx_sql.to_sql_keep_going().wrap_err("SQL generation error")?;
+        let errors = entity_errors
+            .into_iter()
+            .chain(sql_errors.iter().map(|error| error.to_string()))
+            .collect::<Vec<_>>();
+
+        if !errors.is_empty() {
+            for error in &errors {
+                eprintln!("{} {}", "       Error".bold().red(), error);
+            }
+            return Err(eyre!("{} SQL entities failed to translate", errors.len()));
+        }
+    }
+
+    // the generated SQL, if it needs more than writing out as-is
+    let decorated = if transaction_wrap || header.is_some() || footer.is_some() {
+        let mut sql = pgx_sql.to_sql()?;
+        if transaction_wrap {
+            sql = wrap_in_transaction(&sql);
+        }
+        if let Some(header) = header {
+            sql = format!("{}\n{}", header, sql);
+        }
+        if let Some(footer) = footer {
+            sql = format!("{}\n{}", sql, footer);
+        }
+        Some(sql)
+    } else {
+        None
+    };
 
     if let Some(out_path) = path {
         let out_path = out_path.as_ref();
@@ -417,14 +520,23 @@ pub(crate) fn generate_schema(
         if let Some(parent) = out_path.parent() {
             std::fs::create_dir_all(parent).wrap_err("Could not create parent directory")?
         }
-        pgx_sql
-            .to_file(out_path)
-            .wrap_err_with(|| eyre!("Could not write SQL to {}", out_path.display()))?;
+        if let Some(sql) = decorated {
+            std::fs::write(out_path, sql)
+                .wrap_err_with(|| eyre!("Could not write SQL to {}", out_path.display()))?;
+        } else {
+            pgx_sql
+                .to_file(out_path)
+                .wrap_err_with(|| eyre!("Could not write SQL to {}", out_path.display()))?;
+        }
     } else {
         eprintln!("{} SQL entities to {}", "     Writing".bold().green(), "/dev/stdout".cyan(),);
-        pgx_sql
-            .write(&mut std::io::stdout())
-            .wrap_err_with(|| eyre!("Could not write SQL to stdout"))?;
+        if let Some(sql) = decorated {
+            print!("{}", sql);
+        } else {
+            pgx_sql
+                .write(&mut std::io::stdout())
+                .wrap_err_with(|| eyre!("Could not write SQL to stdout"))?;
+        }
     }
 
     if let Some(dot_path) = dot {
@@ -435,6 +547,16 @@ pub(crate) fn generate_schema(
     Ok(())
 }
 
+fn panic_message(panic: &Box<dyn std::any::Any + Send>) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "Box<Any>"
+    }
+}
+
 #[tracing::instrument(level = "error", skip_all, fields(
     postmaster_path = %format_display_path(postmaster_path.as_ref())?,
     postmaster_stub_dir = %format_display_path(postmaster_stub_dir.as_ref())?,
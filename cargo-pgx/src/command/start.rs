@@ -7,13 +7,14 @@ All rights reserved.
 Use of this source code is governed by the MIT license that can be found in the LICENSE file.
 */
 
-use crate::command::init::initdb;
-use crate::command::status::status_postgres;
+use crate::command::init::{initdb, save_password, AuthMethod};
+use crate::command::status::{status_postgres, with_pgdata};
+use crate::command::stop::remove_stale_socket;
 use crate::CommandExecute;
 use cargo_toml::Manifest;
 use eyre::{eyre, WrapErr};
 use owo_colors::OwoColorize;
-use pgx_pg_config::{PgConfig, PgConfigSelector, Pgx};
+use pgx_pg_config::{PgConfig, Pgx};
 use std::os::unix::process::CommandExt;
 use std::path::PathBuf;
 use std::process::Stdio;
@@ -33,6 +34,15 @@ pub(crate) struct Start {
     /// Path to Cargo.toml
     #[clap(long, value_parser)]
     manifest_path: Option<PathBuf>,
+    /// Reconfigure how clients must authenticate to the instance
+    #[clap(long, value_enum)]
+    auth: Option<AuthMethod>,
+    /// The superuser's password, required when `--auth` isn't `trust`
+    #[clap(long, env = "PGPASSWORD", hide_env_values = true)]
+    password: Option<String>,
+    /// Use this data directory rather than the one pgx manages.  Requires a single Postgres version
+    #[clap(long, value_parser)]
+    pgdata: Option<PathBuf>,
 }
 
 impl CommandExecute for Start {
@@ -58,9 +68,15 @@ impl CommandExecute for Start {
             }
         };
 
-        for pg_config in pgx.iter(PgConfigSelector::new(&pg_version)) {
-            let pg_config = pg_config?;
-            start_postgres(pg_config)?
+        if let Some(auth) = self.auth {
+            auth.check_password(self.password.as_deref())?;
+        }
+
+        for pg_config in with_pgdata(&pgx, &pg_version, self.pgdata.as_ref())? {
+            match self.auth {
+                Some(auth) => start_postgres_with_auth(&pg_config, auth, self.password.as_deref())?,
+                None => start_postgres(&pg_config)?,
+            }
         }
 
         Ok(())
@@ -70,12 +86,9 @@ impl CommandExecute for Start {
 #[tracing::instrument(level = "error", skip_all, fields(pg_version = %pg_config.version()?))]
 pub(crate) fn start_postgres(pg_config: &PgConfig) -> eyre::Result<()> {
     let datadir = pg_config.data_dir()?;
-    let logfile = pg_config.log_file()?;
-    let bindir = pg_config.bin_dir()?;
-    let port = pg_config.port()?;
 
     if !datadir.exists() {
-        initdb(&bindir, &datadir)?;
+        initdb(&pg_config.bin_dir()?, &datadir, AuthMethod::Trust, None)?;
     }
 
     if status_postgres(pg_config)? {
@@ -83,6 +96,167 @@ pub(crate) fn start_postgres(pg_config: &PgConfig) -> eyre::Result<()> {
         return Ok(());
     }
 
+    // a crash may have left its socket behind
+    remove_stale_socket(pg_config)?;
+    pg_ctl_start(pg_config)
+}
+
+/// Start Postgres, as [`start_postgres`] does, then make clients authenticate with `auth`
+#[tracing::instrument(level = "error", skip_all, fields(pg_version = %pg_config.version()?, auth = auth.as_str()))]
+pub(crate) fn start_postgres_with_auth(
+    pg_config: &PgConfig,
+    auth: AuthMethod,
+    password: Option<&str>,
+) -> eyre::Result<()> {
+    let datadir = pg_config.data_dir()?;
+
+    if !datadir.exists() {
+        // a brand new data directory is configured by initdb itself
+        initdb(&pg_config.bin_dir()?, &datadir, auth, password)?;
+        save_password(pg_config, auth, password)?;
+        return start_postgres(pg_config);
+    }
+
+    start_postgres(pg_config)?;
+
+    println!(
+        "{} {} authentication for Postgres v{}",
+        " Configuring".bold().green(),
+        auth.as_str().cyan(),
+        pg_config.major_version()?
+    );
+
+    if auth != AuthMethod::Trust {
+        let password =
+            password.ok_or_else(|| eyre!("`--auth {}` requires a password", auth.as_str()))?;
+        set_superuser_password(pg_config, auth, password)?;
+    }
+    save_password(pg_config, auth, password)?;
+
+    let hba_path = datadir.join("pg_hba.conf");
+    let hba = std::fs::read_to_string(&hba_path)
+        .wrap_err_with(|| format!("unable to read `{}`", hba_path.display()))?;
+    std::fs::write(&hba_path, set_hba_auth_method(&hba, auth))
+        .wrap_err_with(|| format!("unable to write `{}`", hba_path.display()))?;
+
+    pg_ctl(pg_config, "reload")
+}
+
+/// Set the superuser's password, hashed as `auth` expects it to be
+fn set_superuser_password(
+    pg_config: &PgConfig,
+    auth: AuthMethod,
+    password: &str,
+) -> eyre::Result<()> {
+    let mut command = std::process::Command::new(pg_config.psql_path()?);
+    command
+        .env_remove("PGUSER")
+        // authenticate with the current password, if there is one
+        .env("PGPASSFILE", Pgx::passfile()?)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .arg("-XqAt")
+        .arg("-h")
+        .arg(pg_config.host())
+        .arg("-p")
+        .arg(pg_config.port()?.to_string())
+        .arg("template1")
+        .arg("-c")
+        .arg(format!(
+            "SET password_encryption = '{}'; ALTER ROLE CURRENT_USER PASSWORD '{}';",
+            auth.as_str(),
+            password.replace("'", "''")
+        ));
+
+    // don't leak the password into logs or error messages
+    let command_str = format!("{:?}", command.get_program());
+    tracing::debug!(command = %command_str, "Running");
+    let output = command.output().wrap_err_with(|| eyre!("unable to execute: {}", command_str))?;
+    tracing::trace!(command = %command_str, status_code = %output.status, "Finished");
+
+    if !output.status.success() {
+        return Err(eyre!(
+            "problem setting the superuser's password: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(())
+}
+
+/// Replace the authentication method of every record in a `pg_hba.conf` file, dropping the
+/// options of the method it replaces
+fn set_hba_auth_method(hba: &str, auth: AuthMethod) -> String {
+    let mut result = String::with_capacity(hba.len());
+    for line in hba.lines() {
+        let (fields, comment) = hba_fields(line);
+        let method_idx = match fields.first().map(|&(start, end)| &line[start..end]) {
+            // `local` records have no address
+            Some("local") => Some(3),
+            Some(kind) if kind.starts_with("host") => {
+                // an IP address without a `/` prefix length is followed by its netmask
+                match fields.get(3).map(|&(start, end)| &line[start..end]) {
+                    Some(address) if address.parse::<std::net::IpAddr>().is_ok() => Some(5),
+                    _ => Some(4),
+                }
+            }
+            _ => None,
+        };
+
+        match method_idx.and_then(|idx| fields.get(idx)) {
+            Some(&(start, _)) => {
+                result.push_str(&line[..start]);
+                result.push_str(auth.as_str());
+                if let Some(comment) = comment {
+                    result.push(' ');
+                    result.push_str(&line[comment..]);
+                }
+            }
+            None => result.push_str(line),
+        }
+        result.push('\n');
+    }
+    result
+}
+
+/// Split a `pg_hba.conf` line into the byte ranges of its fields, which may be double-quoted,
+/// and the offset of its trailing comment, if any
+fn hba_fields(line: &str) -> (Vec<(usize, usize)>, Option<usize>) {
+    let mut fields = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => {
+                if let Some(start) = start {
+                    fields.push((start, idx));
+                }
+                return (fields, Some(idx));
+            }
+            c if c.is_whitespace() && !quoted => {
+                if let Some(start) = start.take() {
+                    fields.push((start, idx));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(idx);
+    }
+    if let Some(start) = start {
+        fields.push((start, line.len()));
+    }
+    (fields, None)
+}
+
+fn pg_ctl_start(pg_config: &PgConfig) -> eyre::Result<()> {
+    let datadir = pg_config.data_dir()?;
+    let logfile = pg_config.log_file()?;
+    let bindir = pg_config.bin_dir()?;
+    let port = pg_config.port()?;
+    check_port_available(port)?;
+
     println!(
         "{} Postgres v{} on port {}",
         "    Starting".bold().green(),
@@ -124,3 +298,76 @@ pub(crate) fn start_postgres(pg_config: &PgConfig) -> eyre::Result<()> {
 
     Ok(())
 }
+
+/// Fail early, rather than leaving the postmaster to log a "could not bind" error, if something
+/// is already listening on `port`
+fn check_port_available(port: u16) -> eyre::Result<()> {
+    match port_in_use(port) {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(eyre!("port {port} is already in use; another process may be bound")),
+        Err(e) => {
+            tracing::debug!(port, error = %e, "Couldn't probe port, starting Postgres anyway");
+            Ok(())
+        }
+    }
+}
+
+/// Whether something is already listening on `port`, on any of the addresses Postgres listens on
+pub(crate) fn port_in_use(port: u16) -> std::io::Result<bool> {
+    // `pg_ctl_start()` passes `-i`, so Postgres listens on every address
+    match std::net::TcpListener::bind(("0.0.0.0", port)) {
+        Ok(_) => Ok(false),
+        Err(e) if e.kind() == std::io::ErrorKind::AddrInUse => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+fn pg_ctl(pg_config: &PgConfig, action: &str) -> eyre::Result<()> {
+    let mut command =
+        std::process::Command::new(format!("{}/pg_ctl", pg_config.bin_dir()?.display()));
+    command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .arg(action)
+        .arg("-D")
+        .arg(pg_config.data_dir()?);
+
+    let command_str = format!("{:?}", command);
+    let output = command.output()?;
+
+    if !output.status.success() {
+        return Err(eyre!(
+            "problem running pg_ctl: {}\n\n{}",
+            command_str,
+            String::from_utf8(output.stderr).unwrap()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hba_auth_method_keeps_addresses_and_netmasks() {
+        let hba = "\
+# TYPE  DATABASE        USER            ADDRESS                 METHOD
+local   all             all                                     trust
+host    all             all             127.0.0.1/32            trust # loopback
+host    all             all             127.0.0.1 255.255.255.255 ident map=\"a b\"
+host    \"my db\"         all             ::1/128                 trust
+";
+        assert_eq!(
+            set_hba_auth_method(hba, AuthMethod::ScramSha256),
+            "\
+# TYPE  DATABASE        USER            ADDRESS                 METHOD
+local   all             all                                     scram-sha-256
+host    all             all             127.0.0.1/32            scram-sha-256 # loopback
+host    all             all             127.0.0.1 255.255.255.255 scram-sha-256
+host    \"my db\"         all             ::1/128                 scram-sha-256
+"
+        );
+    }
+}
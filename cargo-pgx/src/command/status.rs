@@ -10,6 +10,7 @@ Use of this source code is governed by the MIT license that can be found in the
 use eyre::eyre;
 use owo_colors::OwoColorize;
 use pgx_pg_config::{PgConfig, PgConfigSelector, Pgx};
+use serde_derive::Serialize;
 use std::path::PathBuf;
 use std::process::Stdio;
 
@@ -30,6 +31,21 @@ pub(crate) struct Status {
     /// Path to Cargo.toml
     #[clap(long, value_parser)]
     manifest_path: Option<PathBuf>,
+    /// Use this data directory rather than the one pgx manages.  Requires a single Postgres version
+    #[clap(long, value_parser)]
+    pgdata: Option<PathBuf>,
+    /// Print one JSON object per line for each Postgres version, rather than a sentence
+    #[clap(long)]
+    json_lines: bool,
+}
+
+/// A line of `--json-lines` output
+#[derive(Serialize)]
+struct StatusLine {
+    pg_version: String,
+    running: bool,
+    port: u16,
+    pgdata: PathBuf,
 }
 
 impl CommandExecute for Status {
@@ -42,9 +58,16 @@ impl CommandExecute for Status {
             None => "all".to_string(),
         };
 
-        for pg_config in pgx.iter(PgConfigSelector::new(&pg_version)) {
-            let pg_config = pg_config?;
-            if status_postgres(pg_config)? {
+        for pg_config in with_pgdata(&pgx, &pg_version, self.pgdata.as_ref())? {
+            if self.json_lines {
+                let line = StatusLine {
+                    pg_version: pg_config.label()?,
+                    running: status_postgres(&pg_config)?,
+                    port: pg_config.port()?,
+                    pgdata: pg_config.data_dir()?,
+                };
+                println!("{}", serde_json::to_string(&line)?);
+            } else if status_postgres(&pg_config)? {
                 println!("Postgres v{} is {}", pg_config.major_version()?, "running".bold().green())
             } else {
                 println!("Postgres v{} is {}", pg_config.major_version()?, "stopped".bold().red())
@@ -55,6 +78,33 @@ impl CommandExecute for Status {
     }
 }
 
+/// Select the `pg_version` Postgres' from `pgx`, using `pgdata` as the data directory if given,
+/// which only makes sense for a single version
+pub(crate) fn with_pgdata(
+    pgx: &Pgx,
+    pg_version: &str,
+    pgdata: Option<&PathBuf>,
+) -> eyre::Result<Vec<PgConfig>> {
+    let pg_configs = pgx
+        .iter(PgConfigSelector::new(pg_version))
+        .map(|pg_config| pg_config.cloned())
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    match pgdata {
+        None => Ok(pg_configs),
+        Some(pgdata) if pg_configs.len() == 1 => {
+            let pgdata = std::env::current_dir()?.join(pgdata);
+            Ok(pg_configs
+                .into_iter()
+                .map(|pg_config| pg_config.with_data_dir(pgdata.clone()))
+                .collect())
+        }
+        Some(_) => {
+            Err(eyre!("`--pgdata` requires a single Postgres version, not `{}`", pg_version))
+        }
+    }
+}
+
 #[tracing::instrument(level = "error", skip_all, fields(pg_version = %pg_config.version()?))]
 pub(crate) fn status_postgres(pg_config: &PgConfig) -> eyre::Result<bool> {
     let datadir = pg_config.data_dir()?;
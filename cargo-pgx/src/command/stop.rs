@@ -7,12 +7,12 @@ All rights reserved.
 Use of this source code is governed by the MIT license that can be found in the LICENSE file.
 */
 
-use crate::command::status::status_postgres;
+use crate::command::status::{status_postgres, with_pgdata};
 use crate::CommandExecute;
 use cargo_toml::Manifest;
 use eyre::{eyre, WrapErr};
 use owo_colors::OwoColorize;
-use pgx_pg_config::{PgConfig, PgConfigSelector, Pgx};
+use pgx_pg_config::{PgConfig, Pgx};
 use std::path::PathBuf;
 use std::process::Stdio;
 
@@ -31,6 +31,12 @@ pub(crate) struct Stop {
     /// Path to Cargo.toml
     #[clap(long, value_parser)]
     manifest_path: Option<PathBuf>,
+    /// Use this data directory rather than the one pgx manages.  Requires a single Postgres version
+    #[clap(long, value_parser)]
+    pgdata: Option<PathBuf>,
+    /// Once stopped, remove any Unix socket files an unclean shutdown left behind
+    #[clap(long)]
+    remove_socket: bool,
 }
 
 impl CommandExecute for Stop {
@@ -56,9 +62,11 @@ impl CommandExecute for Stop {
             }
         };
 
-        for pg_config in pgx.iter(PgConfigSelector::new(&pg_version)) {
-            let pg_config = pg_config?;
-            stop_postgres(pg_config)?
+        for pg_config in with_pgdata(&pgx, &pg_version, self.pgdata.as_ref())? {
+            stop_postgres(&pg_config)?;
+            if self.remove_socket {
+                remove_stale_socket(&pg_config)?;
+            }
         }
 
         Ok(())
@@ -97,3 +105,38 @@ pub(crate) fn stop_postgres(pg_config: &PgConfig) -> eyre::Result<()> {
         Ok(())
     }
 }
+
+/// Remove the Unix socket, and its lock file, that a Postgres which isn't running left behind.
+///
+/// The socket's directory and port are those recorded in the data directory's `postmaster.pid`,
+/// if a crash left one, and otherwise those pgx starts Postgres with
+pub(crate) fn remove_stale_socket(pg_config: &PgConfig) -> eyre::Result<()> {
+    if status_postgres(pg_config)? {
+        // it's in use
+        return Ok(());
+    }
+
+    let pidfile = pg_config.data_dir()?.join("postmaster.pid");
+    // the 4th and 5th lines of `postmaster.pid` are the port and socket directory
+    let recorded = std::fs::read_to_string(&pidfile).ok().and_then(|contents| {
+        let mut lines = contents.lines().skip(3);
+        let port = lines.next()?.trim().parse::<u16>().ok()?;
+        let socket_dir = lines.next()?.trim();
+        (!socket_dir.is_empty()).then(|| (port, PathBuf::from(socket_dir)))
+    });
+    let (port, socket_dir) = match recorded {
+        Some(recorded) => recorded,
+        None => (pg_config.port()?, Pgx::home()?),
+    };
+
+    let socket = socket_dir.join(format!(".s.PGSQL.{}", port));
+    let lock = socket_dir.join(format!(".s.PGSQL.{}.lock", port));
+    for path in [socket, lock] {
+        if path.exists() {
+            println!("{} stale socket file {}", "    Removing".bold().green(), path.display());
+            std::fs::remove_file(&path)
+                .wrap_err_with(|| format!("couldn't remove `{}`", path.display()))?;
+        }
+    }
+    Ok(())
+}
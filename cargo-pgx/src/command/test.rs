@@ -9,7 +9,9 @@ Use of this source code is governed by the MIT license that can be found in the
 
 use cargo_toml::Manifest;
 use eyre::{eyre, WrapErr};
+use owo_colors::OwoColorize;
 use pgx_pg_config::{get_target_dir, PgConfig, PgConfigSelector, Pgx};
+use rayon::prelude::*;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
@@ -40,6 +42,30 @@ pub(crate) struct Test {
     /// Don't regenerate the schema
     #[clap(long, short)]
     no_schema: bool,
+    /// Run at most this many tests concurrently, and size the test instance's `max_connections` to match
+    #[clap(long, short)]
+    jobs: Option<usize>,
+    /// Skip building, and install the extension shared object already in the target directory
+    #[clap(long)]
+    skip_build: bool,
+    /// Write each test's Postgres server log to `<OUTPUT_DIR>/<testname>.log`
+    #[clap(long, value_parser)]
+    output_dir: Option<PathBuf>,
+    /// Don't capture the tests' output, and also show everything the Postgres server logs
+    #[clap(long)]
+    nocapture: bool,
+    /// Also build with the features listed in the `PGX_FEATURES` environment variable
+    #[clap(long)]
+    features_from_env: bool,
+    /// Run the test suite once per semicolon-separated feature set, e.g. `"a,b;a;c"`, in addition to `--features`
+    #[clap(long)]
+    features_matrix: Option<String>,
+    /// Before testing against each Postgres version, print its `pg_config` path and exact version
+    #[clap(long)]
+    show_server_version: bool,
+    /// Test up to this many Postgres versions at once, each with its own target directory
+    #[clap(long)]
+    version_jobs: Option<usize>,
     #[clap(flatten)]
     features: clap_cargo::Features,
     #[clap(from_global, action = clap::ArgAction::Count)]
@@ -48,8 +74,15 @@ pub(crate) struct Test {
 
 impl CommandExecute for Test {
     #[tracing::instrument(level = "error", skip(self))]
-    fn execute(self) -> eyre::Result<()> {
+    fn execute(mut self) -> eyre::Result<()> {
+        if self.features_from_env {
+            self.features = crate::manifest::features_from_env(self.features);
+        }
         let pgx = Pgx::from_config()?;
+        let features_matrix = match self.features_matrix {
+            Some(ref matrix) => parse_features_matrix(matrix)?,
+            None => vec![vec![]],
+        };
 
         let metadata = crate::metadata::metadata(&self.features, self.manifest_path.as_ref())
             .wrap_err("couldn't get cargo metadata")?;
@@ -66,7 +99,13 @@ impl CommandExecute for Test {
                 .ok_or(eyre!("No provided `pg$VERSION` flag."))?,
         };
         let profile = CargoProfile::from_flags(self.release, self.profile.as_deref())?;
+        // `cargo test` runs from the package's directory, so resolve this against ours
+        let output_dir = match self.output_dir {
+            Some(ref dir) => Some(std::env::current_dir()?.join(dir)),
+            None => None,
+        };
 
+        let mut runs = Vec::new();
         for pg_config in pgx.iter(PgConfigSelector::new(&pg_version)) {
             let mut testname = self.testname.clone();
             let pg_config = match pg_config {
@@ -84,48 +123,134 @@ impl CommandExecute for Test {
                 }
                 Ok(config) => config,
             };
+            runs.push((pg_config, testname));
+        }
+
+        let version_jobs = self.version_jobs.unwrap_or(1).max(1).min(runs.len().max(1));
+        let target_dir = get_target_dir()?;
+        let options = TestOptions {
+            manifest_path: self.manifest_path.as_deref(),
+            package: self.package.as_ref(),
+            profile: &profile,
+            no_schema: self.no_schema,
+            jobs: self.jobs,
+            skip_build: self.skip_build,
+            output_dir: output_dir.as_deref(),
+            nocapture: self.nocapture,
+        };
+
+        // Returns the feature sets which failed
+        let run_version = |(pg_config, testname): &(&PgConfig, Option<String>)| {
             let pg_version = format!("pg{}", pg_config.major_version()?);
+            // concurrent `cargo test`s would contend for the same target directory, so each
+            // version gets its own
+            let target_dir = match version_jobs {
+                1 => target_dir.clone(),
+                _ => target_dir.join(format!("pgx-test-{}", pg_version)),
+            };
 
-            let features = crate::manifest::features_for_version(
-                self.features.clone(),
-                &package_manifest,
-                &pg_version,
-            );
+            if self.show_server_version {
+                println!(
+                    "{} against {} ({})",
+                    "     Testing".bold().green(),
+                    pg_config.version_string()?,
+                    pg_config.resolved_path()?.display()
+                );
+            }
+
+            let mut failed = Vec::new();
+            for feature_set in &features_matrix {
+                let mut features = self.features.clone();
+                features.features.extend(feature_set.iter().cloned());
+                let features =
+                    crate::manifest::features_for_version(features, &package_manifest, &pg_version);
+
+                let passed =
+                    test_extension(pg_config, &options, &features, testname.clone(), &target_dir)?;
+                if !passed {
+                    failed.push(feature_set);
+                }
+            }
+            Ok::<_, eyre::Report>((pg_version, failed))
+        };
+
+        if version_jobs == 1 {
+            for run in &runs {
+                let (pg_version, failed) = run_version(run)?;
+                if self.features_matrix.is_some() {
+                    print_summary(&pg_version, &features_matrix, &failed);
+                }
 
-            test_extension(
-                pg_config,
-                self.manifest_path.as_ref(),
-                self.package.as_ref(),
-                &profile,
-                self.no_schema,
-                &features,
-                testname.clone(),
-            )?
+                if !failed.is_empty() {
+                    // We explicitly do not want to return a spantraced error here.
+                    std::process::exit(1)
+                }
+            }
+        } else {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(version_jobs).build()?;
+            let results = pool.install(|| runs.par_iter().map(run_version).collect::<Vec<_>>());
+
+            // only report once every version has finished, as their output is interleaved
+            let mut any_failed = false;
+            for result in results {
+                let (pg_version, failed) = result?;
+                print_summary(&pg_version, &features_matrix, &failed);
+                any_failed |= !failed.is_empty();
+            }
+
+            if any_failed {
+                // We explicitly do not want to return a spantraced error here.
+                std::process::exit(1)
+            }
         }
 
         Ok(())
     }
 }
 
+/// How to run the test suite, whichever Postgres version and features it's run against
+pub(crate) struct TestOptions<'a> {
+    /// The user's `--manifest-path`
+    pub(crate) manifest_path: Option<&'a Path>,
+    /// The user's `--package`
+    pub(crate) package: Option<&'a String>,
+    pub(crate) profile: &'a CargoProfile,
+    /// Don't regenerate the schema
+    pub(crate) no_schema: bool,
+    /// Run at most this many tests concurrently
+    pub(crate) jobs: Option<usize>,
+    /// Install the extension shared object already in the target directory
+    pub(crate) skip_build: bool,
+    /// Write each test's Postgres server log here
+    pub(crate) output_dir: Option<&'a Path>,
+    pub(crate) nocapture: bool,
+}
+
 #[tracing::instrument(skip_all, fields(
     pg_version = %pg_config.version()?,
     testname =  tracing::field::Empty,
-    ?profile,
+    profile = ?options.profile,
 ))]
-pub fn test_extension(
+pub(crate) fn test_extension(
     pg_config: &PgConfig,
-    user_manifest_path: Option<impl AsRef<Path>>,
-    user_package: Option<&String>,
-    profile: &CargoProfile,
-    no_schema: bool,
+    options: &TestOptions,
     features: &clap_cargo::Features,
     testname: Option<impl AsRef<str>>,
-) -> eyre::Result<()> {
+    target_dir: &Path,
+) -> eyre::Result<bool> {
     if let Some(ref testname) = testname {
         tracing::Span::current().record("testname", &tracing::field::display(&testname.as_ref()));
     }
-    let target_dir = get_target_dir()?;
-
+    let TestOptions {
+        manifest_path: user_manifest_path,
+        package: user_package,
+        profile,
+        no_schema,
+        jobs,
+        skip_build,
+        output_dir,
+        nocapture,
+    } = *options;
     let mut command = Command::new("cargo");
 
     let no_default_features_arg = features.no_default_features;
@@ -138,17 +263,30 @@ pub fn test_extension(
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .arg("test")
-        .env("CARGO_TARGET_DIR", &target_dir)
+        .env("CARGO_TARGET_DIR", target_dir)
         .env("PGX_FEATURES", features_arg.clone())
         .env("PGX_NO_DEFAULT_FEATURES", if no_default_features_arg { "true" } else { "false" })
         .env("PGX_ALL_FEATURES", if features.all_features { "true" } else { "false" })
         .env("PGX_BUILD_PROFILE", profile.name())
-        .env("PGX_NO_SCHEMA", if no_schema { "true" } else { "false" });
+        .env("PGX_NO_SCHEMA", if no_schema { "true" } else { "false" })
+        .env("PGX_SKIP_BUILD", if skip_build { "true" } else { "false" })
+        .env("PGX_TEST_NOCAPTURE", if nocapture { "true" } else { "false" });
 
     if let Ok(rust_log) = std::env::var("RUST_LOG") {
         command.env("RUST_LOG", rust_log);
     }
 
+    if let Some(jobs) = jobs {
+        // limits the libtest harness's threads, and tells the test framework how many
+        // concurrent connections to expect
+        command.env("RUST_TEST_THREADS", jobs.to_string());
+        command.env("PGX_TEST_JOBS", jobs.to_string());
+    }
+
+    if let Some(output_dir) = output_dir {
+        command.env("PGX_TEST_OUTPUT_DIR", output_dir);
+    }
+
     if !features_arg.trim().is_empty() {
         command.arg("--features");
         command.arg(&features_arg);
@@ -166,7 +304,7 @@ pub fn test_extension(
 
     if let Some(user_manifest_path) = user_manifest_path {
         command.arg("--manifest-path");
-        command.arg(user_manifest_path.as_ref());
+        command.arg(user_manifest_path);
     }
 
     if let Some(user_package) = user_package {
@@ -178,17 +316,57 @@ pub fn test_extension(
         command.arg(testname.as_ref());
     }
 
+    if nocapture {
+        command.arg("--").arg("--nocapture");
+    }
+
     eprintln!("{:?}", command);
 
     tracing::debug!(command = ?command, "Running");
     let status = command.status().wrap_err("failed to run cargo test")?;
     tracing::trace!(status_code = %status, command = ?command, "Finished");
-    if !status.success() {
-        if !status.success() {
-            // We explicitly do not want to return a spantraced error here.
-            std::process::exit(1)
+
+    Ok(status.success())
+}
+
+/// Print whether each of `features_matrix` passed when testing `pg_version`
+fn print_summary(pg_version: &str, features_matrix: &[Vec<String>], failed: &[&Vec<String>]) {
+    for feature_set in features_matrix {
+        let result = if failed.contains(&feature_set) {
+            "FAILED".bold().red().to_string()
+        } else {
+            "ok".bold().green().to_string()
+        };
+        if feature_set.is_empty() {
+            println!("{} {}: {}", "      Tested".bold().green(), pg_version, result);
+        } else {
+            println!(
+                "{} {} with features `{}`: {}",
+                "      Tested".bold().green(),
+                pg_version,
+                feature_set.join(","),
+                result
+            );
         }
     }
+}
 
-    Ok(())
+/// Parse a `--features-matrix` value like `"a,b;a;c"` into its feature sets
+fn parse_features_matrix(matrix: &str) -> eyre::Result<Vec<Vec<String>>> {
+    let feature_sets = matrix
+        .split(';')
+        .map(|set| {
+            set.split(',')
+                .map(str::trim)
+                .filter(|feature| !feature.is_empty())
+                .map(String::from)
+                .collect::<Vec<_>>()
+        })
+        .filter(|set| !set.is_empty())
+        .collect::<Vec<_>>();
+
+    if feature_sets.is_empty() {
+        return Err(eyre!("`--features-matrix` must contain at least one feature set"));
+    }
+    Ok(feature_sets)
 }
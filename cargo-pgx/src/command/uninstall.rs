@@ -0,0 +1,116 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::command::get::find_control_file;
+use crate::command::install::format_display_path;
+use crate::CommandExecute;
+use eyre::WrapErr;
+use owo_colors::OwoColorize;
+use pgx_pg_config::PgConfig;
+use std::path::{Path, PathBuf};
+
+/// Remove the files `cargo pgx install` installed for this extension from the Postgres specified by whatever `pg_config` is currently on your $PATH
+#[derive(clap::Args, Debug)]
+#[clap(author)]
+pub(crate) struct Uninstall {
+    /// Package to uninstall (see `cargo help pkgid`)
+    #[clap(long, short)]
+    package: Option<String>,
+    /// Path to Cargo.toml
+    #[clap(long, value_parser)]
+    manifest_path: Option<PathBuf>,
+    /// The `pg_config` path (default is first in $PATH)
+    #[clap(long, short = 'c')]
+    pg_config: Option<String>,
+    #[clap(from_global, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+impl CommandExecute for Uninstall {
+    #[tracing::instrument(level = "error", skip(self))]
+    fn execute(self) -> eyre::Result<()> {
+        let metadata = crate::metadata::metadata(
+            &clap_cargo::Features::default(),
+            self.manifest_path.as_ref(),
+        )
+        .wrap_err("couldn't get cargo metadata")?;
+        crate::metadata::validate(&metadata)?;
+        let package_manifest_path =
+            crate::manifest::manifest_path(&metadata, self.package.as_ref())
+                .wrap_err("Couldn't get manifest path")?;
+
+        let pg_config = match self.pg_config {
+            None => PgConfig::from_path(),
+            Some(config) => PgConfig::new_with_defaults(PathBuf::from(config)),
+        };
+
+        uninstall_extension(&pg_config, &package_manifest_path)
+    }
+}
+
+#[tracing::instrument(skip_all, fields(pg_version = %pg_config.version()?))]
+pub(crate) fn uninstall_extension(
+    pg_config: &PgConfig,
+    package_manifest_path: impl AsRef<Path>,
+) -> eyre::Result<()> {
+    let (_, extname) = find_control_file(&package_manifest_path)?;
+
+    let mut files = installed_files(&pg_config.extension_dir()?, |filename| {
+        filename == format!("{}.control", extname)
+            || (filename.starts_with(&format!("{}--", extname)) && filename.ends_with(".sql"))
+    })?;
+    files.extend(installed_files(&pg_config.pkglibdir()?, |filename| {
+        // either unversioned, or versioned as `install_extension()` names it when the control
+        // file has no `module_pathname`
+        match filename.strip_prefix(&extname).and_then(|rest| rest.strip_suffix(".so")) {
+            Some("") => true,
+            Some(version) => matches!(
+                version.strip_prefix('-'),
+                Some(version) if version.starts_with(|c: char| c.is_ascii_digit())
+            ),
+            None => false,
+        }
+    })?);
+
+    if files.is_empty() {
+        println!("{} no installed files found for {}", "    Finished".bold().green(), extname);
+        return Ok(());
+    }
+
+    for file in files {
+        println!("{} {}", "    Removing".bold().green(), format_display_path(&file)?.cyan());
+        std::fs::remove_file(&file)
+            .wrap_err_with(|| format!("failed to remove `{}`", file.display()))?;
+    }
+
+    println!("{} uninstalling {}", "    Finished".bold().green(), extname);
+    Ok(())
+}
+
+/// The files directly in `dir` whose names are recognized by `belongs`, in name order
+fn installed_files(dir: &Path, belongs: impl Fn(&str) -> bool) -> eyre::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut files = Vec::new();
+    for entry in
+        std::fs::read_dir(dir).wrap_err_with(|| format!("failed to read `{}`", dir.display()))?
+    {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        if matches!(entry.file_name().to_str(), Some(filename) if belongs(filename)) {
+            files.push(entry.path());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
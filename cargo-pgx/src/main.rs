@@ -105,3 +105,39 @@ fn main() -> color_eyre::Result<()> {
 
     cargo_cli.execute()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_verbose_parses_for_every_command() {
+        // a `from_global` field of the wrong type only panics once the arguments are parsed
+        for args in [
+            &["init"][..],
+            &["start", "pg14"],
+            &["stop", "pg14"],
+            &["status", "pg14"],
+            &["logs", "pg14"],
+            &["new", "my_extension"],
+            &["install"],
+            &["uninstall"],
+            &["package"],
+            &["schema", "pg14"],
+            &["run", "pg14"],
+            &["connect", "pg14", "db"],
+            &["psql-file", "pg14", "x.sql", "db"],
+            &["migrate", "check"],
+            &["test", "pg14"],
+            &["get", "relocatable"],
+            &["env"],
+            &["doctor"],
+            &["completions", "bash"],
+        ] {
+            let args = ["cargo", "pgx", "-v"].iter().chain(args);
+            if let Err(e) = CargoCommand::try_parse_from(args) {
+                panic!("{}", e);
+            }
+        }
+    }
+}
@@ -48,11 +48,38 @@ pub(crate) fn default_pg_version(manifest: &Manifest) -> Option<String> {
     None
 }
 
+/// Layer the features listed in the `PGX_FEATURES` environment variable (space- or
+/// comma-separated, like `--features`) under those given explicitly on the command line
+pub(crate) fn features_from_env(mut features: clap_cargo::Features) -> clap_cargo::Features {
+    let from_env = std::env::var("PGX_FEATURES").unwrap_or_default();
+    let mut layered = split_features(&[from_env]);
+    tracing::debug!(features = ?layered, "Read features from `PGX_FEATURES`");
+    for feature in split_features(&features.features) {
+        if !layered.contains(&feature) {
+            layered.push(feature);
+        }
+    }
+    features.features = layered;
+    features
+}
+
+/// Split each of `lists` on commas and whitespace, as cargo does for `--features`, so that
+/// `--features a,b` is understood as two features, just like `--features "a b"`
+fn split_features(lists: &[String]) -> Vec<String> {
+    lists
+        .iter()
+        .flat_map(|list| list.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|feature| !feature.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 pub(crate) fn features_for_version(
     mut features: clap_cargo::Features,
     manifest: &Manifest,
     pg_version: &String,
 ) -> clap_cargo::Features {
+    features.features = split_features(&features.features);
     let default_features = manifest.features.get("default");
 
     match default_features {
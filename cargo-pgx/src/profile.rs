@@ -53,6 +53,12 @@ impl CargoProfile {
         }
     }
 
+    /// The environment variable which overrides `debug-assertions` for this profile, when set on
+    /// a `cargo` command
+    pub fn debug_assertions_env_var(&self) -> String {
+        format!("CARGO_PROFILE_{}_DEBUG_ASSERTIONS", self.name().to_uppercase().replace('-', "_"))
+    }
+
     pub fn target_subdir(&self) -> &str {
         match self {
             Self::Dev => "debug",
@@ -58,7 +58,10 @@ pub fn pg_guard(_attr: TokenStream, item: TokenStream) -> TokenStream {
 #[proc_macro_attribute]
 pub fn pg_test(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut stream = proc_macro2::TokenStream::new();
-    let args = parse_extern_attributes(proc_macro2::TokenStream::from(attr.clone()));
+    let args = match parse_extern_attributes(proc_macro2::TokenStream::from(attr.clone())) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
 
     let mut expected_error = None;
     args.into_iter().for_each(|v| {
@@ -431,10 +434,13 @@ Optionally accepts the following attributes:
 * `immutable`: Corresponds to [`IMMUTABLE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `strict`: Corresponds to [`STRICT`](https://www.postgresql.org/docs/current/sql-createfunction.html).
   + In most cases, `#[pg_extern]` can detect when no `Option<T>`s are used, and automatically set this.
+* `called_on_null_input`: Corresponds to [`CALLED ON NULL INPUT`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+  + Opts out of automatically setting `strict`.  A non-`Option<T>` argument which receives a SQL `NULL` raises an ERROR.
 * `stable`: Corresponds to [`STABLE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `volatile`: Corresponds to [`VOLATILE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `raw`: Corresponds to [`RAW`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `parallel_safe`: Corresponds to [`PARALLEL SAFE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
+  + `parallel` alone is shorthand for `parallel_safe`.  Only one of the `parallel_*` options may be given.
 * `parallel_unsafe`: Corresponds to [`PARALLEL UNSAFE`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `parallel_restricted`: Corresponds to [`PARALLEL RESTRICTED`](https://www.postgresql.org/docs/current/sql-createfunction.html).
 * `no_guard`: Do not use `#[pg_guard]` with the function.
@@ -1030,6 +1036,10 @@ Currently `sql` can be provided one of the following:
 * Call custom SQL generator function with `#[pgx(sql = path::to_function)]`
 * Render a specific fragment of SQL with a string `#[pgx(sql = "CREATE FUNCTION ...")]`
 
+Additional SQL can be appended to whatever is generated for the item, such as a cast for a
+`#[derive(PostgresType)]`, with `#[pgx(generates = "CREATE CAST ...")]`.  On its own this keeps
+the default SQL, and it can be combined with `sql = ..`.
+
 */
 #[proc_macro_attribute]
 pub fn pgx(_attr: TokenStream, item: TokenStream) -> TokenStream {
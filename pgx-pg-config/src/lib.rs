@@ -61,6 +61,7 @@ pub struct PgConfig {
     pg_config: Option<PathBuf>,
     base_port: u16,
     base_testing_port: u16,
+    data_dir: Option<PathBuf>,
 }
 
 impl Display for PgConfig {
@@ -82,6 +83,7 @@ impl Default for PgConfig {
             pg_config: None,
             base_port: BASE_POSTGRES_PORT_NO,
             base_testing_port: BASE_POSTGRES_TESTING_PORT_NO,
+            data_dir: None,
         }
     }
 }
@@ -94,7 +96,13 @@ impl From<PgVersion> for PgConfig {
 
 impl PgConfig {
     pub fn new(pg_config: PathBuf, base_port: u16, base_testing_port: u16) -> Self {
-        PgConfig { version: None, pg_config: Some(pg_config), base_port, base_testing_port }
+        PgConfig {
+            version: None,
+            pg_config: Some(pg_config),
+            base_port,
+            base_testing_port,
+            data_dir: None,
+        }
     }
 
     pub fn new_with_defaults(pg_config: PathBuf) -> Self {
@@ -103,9 +111,16 @@ impl PgConfig {
             pg_config: Some(pg_config),
             base_port: BASE_POSTGRES_PORT_NO,
             base_testing_port: BASE_POSTGRES_TESTING_PORT_NO,
+            data_dir: None,
         }
     }
 
+    /// Use `data_dir` as this Postgres' data directory, rather than the one under `$PGX_HOME`
+    pub fn with_data_dir(mut self, data_dir: PathBuf) -> Self {
+        self.data_dir = Some(data_dir);
+        self
+    }
+
     pub fn from_path() -> Self {
         Self::new_with_defaults("pg_config".into())
     }
@@ -178,6 +193,12 @@ impl PgConfig {
         Ok(version)
     }
 
+    /// The full `pg_config --version` output, such as `PostgreSQL 14.5 (Debian 14.5-1)`, which
+    /// unlike [`PgConfig::version()`] identifies the exact build
+    pub fn version_string(&self) -> eyre::Result<String> {
+        self.run("--version")
+    }
+
     pub fn url(&self) -> Option<&Url> {
         match &self.version {
             Some(version) => Some(&version.url),
@@ -232,6 +253,9 @@ impl PgConfig {
     }
 
     pub fn data_dir(&self) -> eyre::Result<PathBuf> {
+        if let Some(data_dir) = &self.data_dir {
+            return Ok(data_dir.clone());
+        }
         let mut path = Pgx::home()?;
         path.push(format!("data-{}", self.major_version()?));
         Ok(path)
@@ -265,10 +289,33 @@ impl PgConfig {
         Ok(path)
     }
 
-    fn run(&self, arg: &str) -> eyre::Result<String> {
-        let pg_config = self.pg_config.clone().unwrap_or_else(|| {
+    /// The absolute path of the `pg_config` executable which is run, searching `$PATH` for it
+    /// if need be
+    pub fn resolved_path(&self) -> eyre::Result<PathBuf> {
+        let pg_config = self.command_path();
+        let found = if pg_config.components().count() > 1 {
+            Some(pg_config.clone())
+        } else {
+            std::env::var_os("PATH").and_then(|paths| {
+                std::env::split_paths(&paths)
+                    .map(|dir| dir.join(&pg_config))
+                    .find(|candidate| candidate.is_file())
+            })
+        };
+
+        let found = found
+            .ok_or_else(|| eyre!("Unable to find `{}` on $PATH", pg_config.display().yellow()))?;
+        found.canonicalize().wrap_err_with(|| format!("Unable to resolve `{}`", found.display()))
+    }
+
+    fn command_path(&self) -> PathBuf {
+        self.pg_config.clone().unwrap_or_else(|| {
             std::env::var("PG_CONFIG").unwrap_or_else(|_| "pg_config".to_string()).into()
-        });
+        })
+    }
+
+    fn run(&self, arg: &str) -> eyre::Result<String> {
+        let pg_config = self.command_path();
 
         match Command::new(&pg_config).arg(arg).output() {
             Ok(output) => Ok(String::from_utf8(output.stdout).unwrap().trim().to_string()),
@@ -337,35 +384,51 @@ impl Pgx {
             }
             Err(_) => {
                 // we'll get what we need from cargo-pgx' config.toml file
-                let path = Pgx::config_toml()?;
-                if !path.exists() {
-                    return Err(eyre!(
-                        "{} not found.  Have you run `{}` yet?",
-                        path.display(),
-                        "cargo pgx init".bold().yellow()
-                    ));
-                }
-
-                match toml::from_str::<ConfigToml>(&std::fs::read_to_string(&path)?) {
-                    Ok(configs) => {
-                        let mut pgx = Pgx::new(
-                            configs.base_port.unwrap_or(BASE_POSTGRES_PORT_NO),
-                            configs.base_testing_port.unwrap_or(BASE_POSTGRES_TESTING_PORT_NO),
-                        );
-
-                        for (_, v) in configs.configs {
-                            pgx.push(PgConfig::new(v, pgx.base_port, pgx.base_testing_port));
-                        }
-                        Ok(pgx)
-                    }
-                    Err(e) => {
-                        Err(e).wrap_err_with(|| format!("Could not read `{}`", path.display()))
-                    }
+                let configs = Pgx::read_config_toml()?;
+                let mut pgx = Pgx::new(
+                    configs.base_port.unwrap_or(BASE_POSTGRES_PORT_NO),
+                    configs.base_testing_port.unwrap_or(BASE_POSTGRES_TESTING_PORT_NO),
+                );
+
+                for (_, v) in configs.configs {
+                    pgx.push(PgConfig::new(v, pgx.base_port, pgx.base_testing_port));
                 }
+                Ok(pgx)
             }
         }
     }
 
+    /// The `pg_config`s listed in `config.toml`, sorted by the label each was configured under,
+    /// such as `pg14`.  Unlike [`Pgx::from_config()`], this ignores `PGX_PG_CONFIG_PATH` and
+    /// doesn't run any of the `pg_config` binaries, so it works even if some are broken
+    pub fn configured_pg_configs() -> eyre::Result<Vec<(String, PgConfig)>> {
+        let configs = Pgx::read_config_toml()?;
+        let base_port = configs.base_port.unwrap_or(BASE_POSTGRES_PORT_NO);
+        let base_testing_port = configs.base_testing_port.unwrap_or(BASE_POSTGRES_TESTING_PORT_NO);
+
+        let mut pg_configs = configs
+            .configs
+            .into_iter()
+            .map(|(label, path)| (label, PgConfig::new(path, base_port, base_testing_port)))
+            .collect::<Vec<_>>();
+        pg_configs.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Ok(pg_configs)
+    }
+
+    fn read_config_toml() -> eyre::Result<ConfigToml> {
+        let path = Pgx::config_toml()?;
+        if !path.exists() {
+            return Err(eyre!(
+                "{} not found.  Have you run `{}` yet?",
+                path.display(),
+                "cargo pgx init".bold().yellow()
+            ));
+        }
+
+        toml::from_str::<ConfigToml>(&std::fs::read_to_string(&path)?)
+            .wrap_err_with(|| format!("Could not read `{}`", path.display()))
+    }
+
     pub fn push(&mut self, pg_config: PgConfig) {
         self.pg_configs.push(pg_config);
     }
@@ -442,6 +505,14 @@ impl Pgx {
         path.push("config.toml");
         Ok(path)
     }
+
+    /// Get the `.pgpass` file holding the superuser's password for each pgx-managed instance that
+    /// doesn't use `trust` authentication.  Clients pgx runs find it through `PGPASSFILE`
+    pub fn passfile() -> Result<PathBuf, std::io::Error> {
+        let mut path = Pgx::home()?;
+        path.push(".pgpass");
+        Ok(path)
+    }
 }
 
 pub const SUPPORTED_MAJOR_VERSIONS: &[u16] = &[10, 11, 12, 13, 14];
@@ -463,6 +534,7 @@ pub fn createdb(
         .env_remove("PGHOST")
         .env_remove("PGPORT")
         .env_remove("PGUSER")
+        .env("PGPASSFILE", Pgx::passfile()?)
         .arg("-h")
         .arg(pg_config.host())
         .arg("-p")
@@ -504,6 +576,7 @@ fn does_db_exist(pg_config: &PgConfig, dbname: &str) -> eyre::Result<bool> {
     command
         .arg("-XqAt")
         .env_remove("PGUSER")
+        .env("PGPASSFILE", Pgx::passfile()?)
         .arg("-h")
         .arg(pg_config.host())
         .arg("-p")
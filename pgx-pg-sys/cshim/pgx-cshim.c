@@ -51,6 +51,13 @@ void pgx_ereport(int level, int code, char *message, char *file, int lineno, int
                     errmsg("%s", message), errcontext_msg("%s:%d:%d", file, lineno, colno)));
 }
 
+PGDLLEXPORT void pgx_ereport_detail(int level, int code, char *message, char *detail, char *file, int lineno, int colno);
+void pgx_ereport_detail(int level, int code, char *message, char *detail, char *file, int lineno, int colno) {
+    ereport(level,
+            (errcode(code),
+                    errmsg("%s", message), errdetail("%s", detail), errcontext_msg("%s:%d:%d", file, lineno, colno)));
+}
+
 PGDLLEXPORT void pgx_SET_VARSIZE(struct varlena *ptr, int size);
 void pgx_SET_VARSIZE(struct varlena *ptr, int size) {
     SET_VARSIZE(ptr, size);
@@ -143,6 +150,11 @@ int *pgx_ARR_DIMS(ArrayType *arr){
     return ARR_DIMS(arr);
 }
 
+PGDLLEXPORT int *pgx_ARR_LBOUND(ArrayType *arr);
+int *pgx_ARR_LBOUND(ArrayType *arr){
+    return ARR_LBOUND(arr);
+}
+
 PGDLLEXPORT void pgx_SpinLockInit(volatile slock_t *lock);
 void pgx_SpinLockInit(volatile slock_t *lock) {
     SpinLockInit(lock);
@@ -9,7 +9,7 @@ Use of this source code is governed by the MIT license that can be found in the
 #![deny(unsafe_op_in_unsafe_fn)]
 #![allow(non_snake_case)]
 
-use crate::FlushErrorState;
+use crate::{geterrcode, FlushErrorState};
 use std::any::Any;
 use std::cell::Cell;
 use std::mem;
@@ -25,7 +25,15 @@ extern "C" {
         lineno: i32,
         colno: i32,
     );
-
+    fn pgx_ereport_detail(
+        level: i32,
+        code: i32,
+        message: *const std::os::raw::c_char,
+        detail: *const std::os::raw::c_char,
+        file: *const std::os::raw::c_char,
+        lineno: i32,
+        colno: i32,
+    );
 }
 
 #[derive(Clone, Debug)]
@@ -62,12 +70,32 @@ fn take_panic_location() -> PanicLocation {
     })
 }
 
+// with the `panic-backtrace` feature, debug builds also remember where the panic came from so it
+// can be reported as the ERROR's DETAIL
+#[cfg(all(feature = "panic-backtrace", debug_assertions))]
+thread_local! { static PANIC_BACKTRACE: Cell<Option<String>> = Cell::new(None) }
+
+#[cfg(all(feature = "panic-backtrace", debug_assertions))]
+fn take_panic_backtrace() -> Option<String> {
+    PANIC_BACKTRACE.with(|b| b.take())
+}
+
+#[cfg(not(all(feature = "panic-backtrace", debug_assertions)))]
+fn take_panic_backtrace() -> Option<String> {
+    None
+}
+
 pub fn register_pg_guard_panic_hook() {
     std::panic::set_hook(Box::new(|info| {
         PANIC_LOCATION.with(|p| {
             let existing = p.take();
 
             p.replace(if existing.is_none() {
+                #[cfg(all(feature = "panic-backtrace", debug_assertions))]
+                PANIC_BACKTRACE.with(|b| {
+                    b.replace(Some(std::backtrace::Backtrace::force_capture().to_string()))
+                });
+
                 match info.location() {
                     Some(location) => Some(PanicLocation {
                         file: location.file().to_string(),
@@ -137,6 +165,50 @@ impl<T> PgTryResult<T> {
         }
     }
 
+    /// The SQLSTATE error code, as made by Postgres' `MAKE_SQLSTATE()`, of the Postgres ERROR the
+    /// try block raised.
+    ///
+    /// Returns `None` if the try block succeeded, or if it raised a Rust panic, which isn't
+    /// converted into an ERROR until it's rethrown
+    pub fn sqlerrcode(&self) -> Option<i32> {
+        match &self.0 {
+            Err(e) if e.is::<JumpContext>() => {
+                // SAFETY: the caught ERROR is still on top of Postgres' error stack, as we only
+                // call `FlushErrorState()` once it's been handled
+                Some(unsafe { geterrcode() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Perform some cleanup operation after the try block if it raised a Postgres ERROR with one
+    /// of the given SQLSTATE error codes, such as a `pgx::PgSqlErrorCode`.
+    ///
+    /// Any other ERROR, or a Rust panic, is rethrown.  This allows, for example, a datum
+    /// validator to recover from `ERRCODE_INVALID_TEXT_REPRESENTATION` without masking something
+    /// more serious, such as running out of memory.
+    ///
+    /// ## Safety
+    ///
+    /// This function does not rethrow a caught ERROR with one of the given codes.  You better
+    /// know what you're doing when you call this function.
+    ///
+    /// Ignoring a caught error can leave Postgres in an undefined state and ultimately cause it
+    /// to crash.
+    pub unsafe fn unwrap_or_else_sqlerrcode<C, F>(self, sqlerrcodes: &[C], cleanup: F) -> T
+    where
+        C: Copy + Into<i32>,
+        F: FnOnce() -> T,
+    {
+        match self.sqlerrcode() {
+            Some(code) if sqlerrcodes.iter().any(|&c| c.into() == code) => {
+                // SAFETY: Caller asserts it is okay to avoid rethrowing an ERROR with this code.
+                unsafe { self.unwrap_or_else(cleanup) }
+            }
+            _ => self.unwrap_or_rethrow(|| {}),
+        }
+    }
+
     /// Perform some operation cleanup operation after the try block if an error was thrown.
     ///
     /// In the event an error was caught, it is rethrown.
@@ -229,15 +301,31 @@ where
             let c_message = std::ffi::CString::new(message).unwrap();
             let c_file = std::ffi::CString::new(location.file).unwrap();
 
-            unsafe {
-                pgx_ereport(
-                    crate::ERROR as i32,
-                    2600, // ERRCODE_INTERNAL_ERROR
-                    c_message.as_ptr(),
-                    c_file.as_ptr(),
-                    location.line as i32,
-                    location.col as i32,
-                );
+            match take_panic_backtrace() {
+                Some(backtrace) => {
+                    let c_detail = std::ffi::CString::new(backtrace.replace('\0', "")).unwrap();
+                    unsafe {
+                        pgx_ereport_detail(
+                            crate::ERROR as i32,
+                            2600, // ERRCODE_INTERNAL_ERROR
+                            c_message.as_ptr(),
+                            c_detail.as_ptr(),
+                            c_file.as_ptr(),
+                            location.line as i32,
+                            location.col as i32,
+                        );
+                    }
+                }
+                None => unsafe {
+                    pgx_ereport(
+                        crate::ERROR as i32,
+                        2600, // ERRCODE_INTERNAL_ERROR
+                        c_message.as_ptr(),
+                        c_file.as_ptr(),
+                        location.line as i32,
+                        location.col as i32,
+                    );
+                },
             }
             unreachable!("ereport() failed at depth==0");
         }
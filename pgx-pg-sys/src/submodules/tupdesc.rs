@@ -32,6 +32,10 @@ impl crate::FormData_pg_attribute {
         self.attisdropped
     }
 
+    pub fn is_not_null(&self) -> bool {
+        self.attnotnull
+    }
+
     pub fn rel_id(&self) -> crate::Oid {
         self.attrelid
     }
@@ -18,7 +18,7 @@ use postgres::error::DbError;
 use std::collections::HashMap;
 use std::fmt::Write as _;
 use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 type LogLines = Arc<Mutex<HashMap<String, Vec<String>>>>;
@@ -36,6 +36,9 @@ static TEST_MUTEX: Lazy<Mutex<SetupState>> = Lazy::new(|| {
         system_session_id: "NONE".to_string(),
     })
 });
+// per-session log files requested via `cargo pgx test --output-dir`, keyed by session id
+static LOG_FILES: Lazy<Mutex<HashMap<String, std::fs::File>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
 static SHUTDOWN_HOOKS: Lazy<Mutex<Vec<Box<dyn Fn() + Send>>>> =
     Lazy::new(|| Mutex::new(Vec::new()));
 
@@ -125,6 +128,10 @@ pub fn run_test(
 
     let (mut client, session_id) = client()?;
 
+    if let Some(output_dir) = test_output_dir() {
+        capture_session_log(&session_id, &output_dir, sql_funcname, &loglines)?;
+    }
+
     let schema = "tests"; // get_extension_schema();
     let result = match client.transaction() {
         // run the test function in a transaction
@@ -209,6 +216,35 @@ fn format_loglines(session_id: &str, loglines: &LogLines) -> String {
     result
 }
 
+fn test_output_dir() -> Option<PathBuf> {
+    std::env::var_os("PGX_TEST_OUTPUT_DIR").map(PathBuf::from)
+}
+
+/// Start writing the log lines of `session_id` to `{output_dir}/{test_name}.log`.  Lines the
+/// session has already logged are written immediately and the rest are appended by
+/// `monitor_pg()` as Postgres emits them.
+fn capture_session_log(
+    session_id: &str,
+    output_dir: &Path,
+    test_name: &str,
+    loglines: &LogLines,
+) -> eyre::Result<()> {
+    std::fs::create_dir_all(output_dir).wrap_err_with(|| {
+        format!("couldn't create test output directory `{}`", output_dir.display())
+    })?;
+    let path = output_dir.join(format!("{test_name}.log"));
+    let mut file = std::fs::File::create(&path)
+        .wrap_err_with(|| format!("couldn't create test log file `{}`", path.display()))?;
+
+    // hold the loglines lock so `monitor_pg()` can't log a line between the two steps
+    let loglines = loglines.lock().unwrap();
+    for line in loglines.get(session_id).into_iter().flatten() {
+        writeln!(file, "{line}")?;
+    }
+    LOG_FILES.lock().unwrap().insert(session_id.to_string(), file);
+    Ok(())
+}
+
 fn initialize_test_framework(
     postgresql_conf: Vec<&'static str>,
 ) -> eyre::Result<(LogLines, String)> {
@@ -299,6 +335,7 @@ fn install_extension() -> eyre::Result<()> {
     eprintln!("installing extension");
     let profile = std::env::var("PGX_BUILD_PROFILE").unwrap_or("debug".into());
     let no_schema = std::env::var("PGX_NO_SCHEMA").unwrap_or("false".into()) == "true";
+    let skip_build = std::env::var("PGX_SKIP_BUILD").unwrap_or("false".into()) == "true";
     let mut features = std::env::var("PGX_FEATURES").unwrap_or("".to_string());
     if !features.contains("pg_test") {
         features += " pg_test";
@@ -360,6 +397,10 @@ fn install_extension() -> eyre::Result<()> {
         command.arg("--no-schema");
     }
 
+    if skip_build {
+        command.arg("--skip-build");
+    }
+
     let command_str = format!("{:?}", command);
 
     let child = command.spawn().wrap_err_with(|| {
@@ -446,6 +487,15 @@ fn modify_postgresql_conf(
         .write_all("log_line_prefix='[%m] [%p] [%c]: '\n".as_bytes())
         .wrap_err("couldn't append log_line_prefix")?;
 
+    if let Ok(jobs) = std::env::var("PGX_TEST_JOBS") {
+        let jobs = jobs.parse::<usize>().wrap_err("PGX_TEST_JOBS is not a number")?;
+        // each concurrent test holds one connection.  leave headroom for the framework's own
+        // connections and `superuser_reserved_connections`
+        postgresql_conf_file
+            .write_all(format!("max_connections = {}\n", jobs + 10).as_bytes())
+            .wrap_err("couldn't append max_connections")?;
+    }
+
     for setting in postgresql_conf {
         postgresql_conf_file
             .write_all(format!("{setting}\n").as_bytes())
@@ -511,6 +561,8 @@ fn monitor_pg(mut command: Command, cmd_string: String, loglines: LogLines) -> (
 
         let regex = regex::Regex::new(r#"\[.*?\] \[.*?\] \[(?P<session_id>.*?)\]"#).unwrap();
         let mut is_started_yet = false;
+        // `cargo pgx test --nocapture` wants to see everything the server logs
+        let nocapture = std::env::var("PGX_TEST_NOCAPTURE").unwrap_or_default() == "true";
         let mut lines = reader.lines();
         while let Some(Ok(line)) = lines.next() {
             let session_id = match get_named_capture(&regex, "session_id", &line) {
@@ -524,7 +576,7 @@ fn monitor_pg(mut command: Command, cmd_string: String, loglines: LogLines) -> (
                 is_started_yet = true;
             }
 
-            if !is_started_yet || line.contains("TMSG: ") {
+            if !is_started_yet || nocapture || line.contains("TMSG: ") {
                 eprintln!("{}", line.cyan());
             }
 
@@ -543,6 +595,9 @@ fn monitor_pg(mut command: Command, cmd_string: String, loglines: LogLines) -> (
             // }
 
             let mut loglines = loglines.lock().unwrap();
+            if let Some(file) = LOG_FILES.lock().unwrap().get_mut(&session_id) {
+                writeln!(file, "{line}").ok();
+            }
             let session_lines = loglines.entry(session_id).or_insert_with(Vec::new);
             session_lines.push(line);
         }
@@ -9,7 +9,7 @@ Use of this source code is governed by the MIT license that can be found in the
 
 use pgx::array::RawArray;
 use pgx::prelude::*;
-use pgx::{Array, Json};
+use pgx::{Array, Inet, Json};
 use serde_json::*;
 
 #[pg_extern(name = "sum_array")]
@@ -104,6 +104,25 @@ fn return_zero_length_vec() -> Vec<i32> {
     Vec::new()
 }
 
+#[pg_extern]
+fn return_inet_array(n: i32) -> Vec<Option<Inet>> {
+    (0..n)
+        .map(|i| match i % 10 {
+            0 => None,
+            _ => Some(Inet(format!("10.0.{}.{}/24", i / 256, i % 256))),
+        })
+        .collect()
+}
+
+#[pg_extern]
+fn array_shape(values: Array<i32>) -> Json {
+    Json(json! {{
+        "ndim": values.ndim(),
+        "dims": values.dims(),
+        "lower_bounds": values.lower_bounds(),
+    }})
+}
+
 #[pg_extern]
 fn get_arr_nelems(arr: Array<i32>) -> libc::c_int {
     // SAFETY: Eh it's fine, it's just a len check.
@@ -301,6 +320,18 @@ mod tests {
         assert!(rc)
     }
 
+    #[pg_test]
+    fn test_return_inet_array() {
+        let rc = Spi::get_one::<bool>(
+            "SELECT return_inet_array(5000) = ARRAY(
+                SELECT CASE WHEN i % 10 = 0 THEN NULL ELSE format('10.0.%s.%s/24', i / 256, i % 256)::inet END
+                  FROM generate_series(0, 4999) i ORDER BY i
+            );",
+        )
+        .expect("failed to get SPI result");
+        assert!(rc)
+    }
+
     #[pg_test]
     fn test_slice_to_array() {
         let owned_vec = vec![Some(1), Some(2), Some(3), None, Some(4)];
@@ -381,6 +412,23 @@ mod tests {
         assert_eq!(vals, &[1, 2, 3, 4, 5]);
     }
 
+    #[pg_test]
+    fn test_array_shape() {
+        let shape = |array: &str| {
+            Spi::get_one::<Json>(&format!("SELECT array_shape('{}'::int[])", array))
+                .expect("returned json was null")
+                .0
+        };
+
+        assert_eq!(shape("{1,2,3}"), json! {{"ndim": 1, "dims": [3], "lower_bounds": [1]}});
+        assert_eq!(shape("[2:4]={1,2,3}"), json! {{"ndim": 1, "dims": [3], "lower_bounds": [2]}});
+        assert_eq!(
+            shape("[0:1][-1:1]={{1,2,3},{4,5,6}}"),
+            json! {{"ndim": 2, "dims": [2, 3], "lower_bounds": [0, -1]}}
+        );
+        assert_eq!(shape("{}"), json! {{"ndim": 0, "dims": [], "lower_bounds": []}});
+    }
+
     #[pg_test]
     fn test_arr_to_vec() {
         let result = Spi::get_one::<Vec<i32>>("SELECT arr_mapped_vec(ARRAY[3,2,2,1]::integer[])");
@@ -14,6 +14,7 @@ mod tests {
     use crate as pgx_tests;
 
     use pgx::prelude::*;
+    use pgx::{Bytea, ByteaFormat};
 
     #[pg_extern]
     fn return_bytes() -> &'static [u8] {
@@ -62,4 +63,26 @@ mod tests {
             .expect("SPI result was null");
         assert_eq!(vec.as_slice(), b"bcd")
     }
+
+    #[pg_test]
+    fn test_bytea_to_text() {
+        let bytea = Bytea(b"a\\b\0\xff~".to_vec());
+        assert_eq!(bytea.to_text(ByteaFormat::Hex), "\\x615c6200ff7e");
+        assert_eq!(bytea.to_text(ByteaFormat::Escape), "a\\\\b\\000\\377~");
+
+        Spi::run("SET LOCAL bytea_output TO 'escape'");
+        assert_eq!(bytea.to_text(ByteaFormat::Session), bytea.to_text(ByteaFormat::Escape));
+        let escaped = Spi::get_one::<String>(r"SELECT '\x615c6200ff7e'::bytea::text")
+            .expect("SPI result was null");
+        assert_eq!(escaped, bytea.to_text(ByteaFormat::Escape));
+
+        Spi::run("SET LOCAL bytea_output TO 'hex'");
+        assert_eq!(bytea.to_text(ByteaFormat::Session), bytea.to_text(ByteaFormat::Hex));
+    }
+
+    #[pg_test]
+    fn test_bytea_roundtrip() {
+        let bytea = Spi::get_one::<Bytea>("SELECT 'abc'::bytea").expect("SPI result was null");
+        assert_eq!(&*bytea, b"abc");
+    }
 }
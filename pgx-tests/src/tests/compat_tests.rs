@@ -0,0 +1,40 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgx::pg_schema]
+mod tests {
+    #[allow(unused_imports)]
+    use crate as pgx_tests;
+
+    use pgx::compat;
+    use pgx::prelude::*;
+
+    #[pg_test]
+    fn test_table_open_and_close() {
+        Spi::run("CREATE TABLE tests.compat_table_open (id int)");
+        let oid = Spi::get_one::<pg_sys::Oid>("SELECT 'tests.compat_table_open'::regclass::oid")
+            .expect("relation does not exist");
+
+        unsafe {
+            let relation = compat::table_open(oid, pg_sys::AccessShareLock as _);
+            assert_eq!(relation.as_ref().unwrap().rd_id, oid);
+            compat::table_close(relation, pg_sys::AccessShareLock as _);
+        }
+    }
+
+    #[pg_test]
+    fn test_next_xid_and_epoch() {
+        let xid = unsafe { pg_sys::GetCurrentTransactionId() };
+        let (next_xid, epoch) = compat::next_xid_and_epoch();
+
+        assert!(next_xid > xid);
+        assert_eq!(epoch, 0);
+    }
+}
@@ -39,6 +39,16 @@ fn convert_timetz_to_time(t: TimeWithTimeZone) -> Time {
     t.to_utc().into()
 }
 
+#[pg_extern]
+fn accept_system_time(t: std::time::SystemTime) -> std::time::SystemTime {
+    t
+}
+
+#[pg_extern]
+fn accept_duration(d: std::time::Duration) -> std::time::Duration {
+    d
+}
+
 #[pg_extern]
 fn accept_timestamp(t: Timestamp) -> Timestamp {
     t
@@ -161,6 +171,7 @@ mod tests {
     use crate as pgx_tests;
 
     use pgx::prelude::*;
+    use pgx::IntoDatum;
     use serde_json::*;
     use std::time::Duration;
     use time;
@@ -471,4 +482,59 @@ mod tests {
             .expect("failed to get SPI result");
         assert!(ts.is_neg_infinity());
     }
+
+    #[pg_test]
+    fn test_system_time_epochs() {
+        let unix_epoch =
+            Spi::get_one::<std::time::SystemTime>("SELECT '1970-01-01 00:00:00+00'::timestamptz")
+                .expect("failed to get SPI result");
+        assert_eq!(std::time::UNIX_EPOCH, unix_epoch);
+
+        let pg_epoch = std::time::UNIX_EPOCH + Duration::from_secs(946_684_800);
+        let result = Spi::get_one_with_args::<bool>(
+            "SELECT $1 = '2000-01-01 00:00:00+00'::timestamptz",
+            vec![(PgBuiltInOids::TIMESTAMPTZOID.oid(), pg_epoch.into_datum())],
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_test]
+    fn test_accept_system_time_round_trip() {
+        let result = Spi::get_one::<bool>(
+            "SELECT accept_system_time('1969-07-20 20:17:40.123456+00') = '1969-07-20 20:17:40.123456+00'::timestamptz",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_test(error = "an infinite timestamp with time zone can't be represented as a SystemTime")]
+    fn test_accept_system_time_infinity() {
+        Spi::get_one::<std::time::SystemTime>("SELECT 'infinity'::timestamptz");
+    }
+
+    #[pg_test]
+    fn test_accept_duration_round_trip() {
+        let result = Spi::get_one::<bool>(
+            "SELECT accept_duration('2 days 3 hours 4.5 seconds') = '51 hours 4.5 seconds'::interval",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+
+        let duration = Spi::get_one::<Duration>("SELECT '1 day 00:00:01.000001'::interval")
+            .expect("failed to get SPI result");
+        assert_eq!(Duration::from_micros(86_401_000_001), duration);
+    }
+
+    #[pg_test(
+        error = "an interval of 1 month(s) can't be represented as a Duration, as months vary in length"
+    )]
+    fn test_accept_duration_months() {
+        Spi::get_one::<Duration>("SELECT '1 month'::interval");
+    }
+
+    #[pg_test(error = "a negative interval can't be represented as a Duration")]
+    fn test_accept_duration_negative() {
+        Spi::get_one::<Duration>("SELECT '-1 second'::interval");
+    }
 }
@@ -259,6 +259,25 @@ mod tests {
         assert_eq!(result, '🚨');
     }
 
+    #[pg_test]
+    unsafe fn test_char_from_text() {
+        let result = Spi::get_one::<char>("SELECT 'é'::text;").expect("SPI result was NULL");
+        assert_eq!(result, 'é');
+    }
+
+    #[pg_test]
+    unsafe fn test_char_from_varchar() {
+        let result = Spi::get_one::<char>("SELECT 'é'::varchar;").expect("SPI result was NULL");
+        assert_eq!(result, 'é');
+        assert!(char::is_compatible_with(pg_sys::VARCHAROID));
+        assert!(char::is_compatible_with(pg_sys::TEXTOID));
+    }
+
+    #[pg_test(error = "expected exactly one character, but got \"ab\"")]
+    unsafe fn test_takes_char_rejects_longer_text() {
+        Spi::get_one::<char>("SELECT takes_char('ab');");
+    }
+
     #[pg_test]
     unsafe fn test_takes_option_with_null_arg() {
         let result = direct_pg_extern_function_call::<i32>(super::takes_option_wrapper, vec![None]);
@@ -14,7 +14,7 @@ mod tests {
     use crate as pgx_tests;
 
     use pgx::prelude::*;
-    use pgx::Inet;
+    use pgx::{Cidr, FromDatum, Inet, InetBinaryError, InetRef, IntoDatum};
 
     #[pg_test]
     fn test_deserialize_inet() {
@@ -43,4 +43,308 @@ mod tests {
         .expect("failed to get SPI result");
         assert!(rc)
     }
+
+    #[pg_test]
+    fn test_inet_ref() {
+        let datum = Inet("192.168.0.1/24".to_owned()).into_datum();
+        let inet = unsafe { InetRef::from_datum(datum.unwrap(), false) }.unwrap();
+        assert_eq!(inet.as_str(), "192.168.0.1/24");
+        assert_eq!(Inet::from(inet), Inet("192.168.0.1/24".to_owned()));
+        assert!(unsafe { InetRef::from_datum(pg_sys::Datum::from(0usize), true) }.is_none());
+    }
+
+    #[pg_test]
+    fn test_borrowed_inet_into_datum() {
+        let inet = Inet("10.0.0.1/8".to_owned());
+        let datum = (&inet).into_datum();
+        let inet_ref = unsafe { InetRef::from_datum(datum.unwrap(), false) }.unwrap();
+        assert_eq!(inet_ref.as_str(), "10.0.0.1/8");
+
+        let datum = inet_ref.into_datum();
+        assert_eq!(unsafe { Inet::from_datum(datum.unwrap(), false) }, Some(inet));
+    }
+
+    #[pg_test]
+    fn test_inet_derivations() {
+        let inet = Inet("192.168.1.5/24".to_owned());
+        assert_eq!("192.168.1.5", inet.host());
+        assert_eq!("192.168.1.0/24", &inet.network().0);
+        assert_eq!("192.168.1.255/24", &inet.broadcast().0);
+        assert_eq!("255.255.255.0", &inet.netmask().0);
+        assert_eq!(24, inet.masklen());
+    }
+
+    #[pg_test]
+    fn test_inet_cidr_conversions() {
+        let inet = Inet("192.168.1.5/24".to_owned());
+        assert_eq!("192.168.1.5/24", inet.abbrev());
+        assert_eq!("192.168.1.0/24", &inet.to_cidr().0);
+
+        let cidr = Cidr("10.1.0.0/16".to_owned());
+        assert_eq!("10.1/16", cidr.abbrev());
+        assert_eq!("10.1.0.0/16", &cidr.to_inet().0);
+    }
+
+    #[pg_test]
+    fn test_inet_any_eq() {
+        let set = vec![Inet("10.0.0.1".to_owned()), Inet("192.168.1.5/24".to_owned())];
+        assert!(Inet("192.168.1.5/24".to_owned()).any_eq(&set));
+        assert!(Inet("10.0.0.1/32".to_owned()).any_eq(&set));
+        // equality is netmask-aware, even though the hosts are the same
+        assert!(!Inet("192.168.1.5".to_owned()).any_eq(&set));
+        assert!(!Inet("10.0.0.1".to_owned()).any_eq(&[]));
+    }
+
+    #[pg_test]
+    fn test_inet_all_contains() {
+        let set = vec![Inet("192.168.1.5".to_owned()), Inet("192.168.1.0/25".to_owned())];
+        assert!(Inet("192.168.1.0/24".to_owned()).all_contains(&set));
+        assert!(!Inet("192.168.1.0/25".to_owned()).all_contains(&set));
+        assert!(Inet("192.168.1.0/24".to_owned()).all_contains(&[]));
+    }
+
+    #[pg_test]
+    fn test_inet_overlaps() {
+        let net = Inet("192.168.1.0/24".to_owned());
+        assert!(net.overlaps(&Inet("192.168.1.5".to_owned())));
+        assert!(Inet("192.168.1.5".to_owned()).overlaps(&net));
+        assert!(net.overlaps(&Inet("192.168.0.0/16".to_owned())));
+        assert!(net.overlaps(&net));
+        assert!(!net.overlaps(&Inet("192.168.2.0/24".to_owned())));
+
+        let cidr = Cidr("10.0.0.0/8".to_owned());
+        assert!(cidr.overlaps(&Cidr("10.1.0.0/16".to_owned())));
+        assert!(!cidr.overlaps(&Cidr("11.0.0.0/8".to_owned())));
+    }
+
+    #[pg_test]
+    fn test_inet_binary() {
+        let inet = Inet("192.168.0.1/24".to_owned());
+        // family, bits, is_cidr, address length, address
+        assert_eq!(inet.to_binary(), vec![2, 24, 0, 4, 192, 168, 0, 1]);
+        assert_eq!(Inet::from_binary(&inet.to_binary()), Ok(inet));
+
+        let inet = Inet("2001:db8::1/64".to_owned());
+        assert_eq!(Inet::from_binary(&inet.to_binary()), Ok(inet));
+
+        assert_eq!(
+            Inet::from_binary(&[9, 24, 0, 4, 192, 168, 0, 1]),
+            Err(InetBinaryError::InvalidBinaryRepresentation)
+        );
+        assert_eq!(Inet::from_binary(&[2, 24]), Err(InetBinaryError::InvalidBinaryRepresentation));
+        assert_eq!(
+            Inet::from_binary(&[2, 24, 0, 4, 192, 168, 0, 1, 0]),
+            Err(InetBinaryError::TrailingBytes(1))
+        );
+    }
+
+    #[pg_test]
+    fn test_cidr_merge() {
+        let cidrs = |values: &[&str]| {
+            values.iter().map(|value| Cidr(value.to_string())).collect::<Vec<_>>()
+        };
+
+        let merged = Cidr::merge(&cidrs(&[
+            "10.0.1.0/24",
+            "192.168.0.0/16",
+            "10.0.0.128/25",
+            "192.168.4.0/24",
+            "10.0.0.0/25",
+            "::/0",
+            "10.0.2.0/24",
+        ]));
+        assert_eq!(merged, cidrs(&["10.0.0.0/23", "10.0.2.0/24", "192.168.0.0/16", "::/0"]));
+
+        // neighbours that don't share a supernet stay apart
+        let merged = Cidr::merge(&cidrs(&["10.0.1.0/24", "10.0.2.0/24"]));
+        assert_eq!(merged, cidrs(&["10.0.1.0/24", "10.0.2.0/24"]));
+
+        assert_eq!(Cidr::merge(&cidrs(&["0.0.0.0/0", "0.0.0.0/0"])), cidrs(&["0.0.0.0/0"]));
+        assert!(Cidr::merge(&[]).is_empty());
+    }
+
+    #[pg_test]
+    fn test_inet_arithmetic() {
+        let addr = || Inet("192.168.1.6".to_owned());
+        assert_eq!(!addr(), Inet("63.87.254.249".to_owned()));
+        assert_eq!(addr() & Inet("0.0.0.255".to_owned()), Inet("0.0.0.6".to_owned()));
+        assert_eq!(addr() | Inet("0.0.0.255".to_owned()), Inet("192.168.1.255".to_owned()));
+        assert_eq!(addr() + 25, Inet("192.168.1.31".to_owned()));
+        assert_eq!(addr() - 6, Inet("192.168.1.0".to_owned()));
+        assert_eq!(Inet("192.168.1.43".to_owned()) - addr(), 37);
+    }
+
+    #[pg_test(error = "cannot AND inet values of different sizes")]
+    fn test_inet_bitand_mixed_families() {
+        let _ = Inet("192.168.1.6".to_owned()) & Inet("::1".to_owned());
+    }
+
+    #[pg_test]
+    fn test_inet_ordering_matches_postgres() {
+        Spi::run(
+            "CREATE TEMP TABLE inet_ordering (value inet);
+             INSERT INTO inet_ordering VALUES ('10.0.0.2'), ('9.0.0.1'), ('10.0.0.0/8'),
+                ('10.0.0.0/16'), ('192.168.1.5/24'), ('192.168.1.5'), ('::1'), ('::ffff:1.2.3.4'),
+                ('0.0.0.0/0'), ('255.255.255.255'), ('2001:db8::/32');",
+        );
+
+        let mut values = Spi::get_one::<Vec<Inet>>("SELECT array_agg(value) FROM inet_ordering")
+            .expect("no inet values");
+        values.sort();
+        let ordered =
+            Spi::get_one::<Vec<Inet>>("SELECT array_agg(value ORDER BY value) FROM inet_ordering")
+                .expect("no inet values");
+
+        assert_eq!(
+            values.iter().map(|inet| inet.0.as_str()).collect::<Vec<_>>(),
+            ordered.iter().map(|inet| inet.0.as_str()).collect::<Vec<_>>()
+        );
+        // numerically, not textually, ordered
+        assert!(Inet("9.0.0.1".to_owned()) < Inet("10.0.0.2".to_owned()));
+        // every IPv4 address sorts before every IPv6 address
+        assert!(Inet("255.255.255.255".to_owned()) < Inet("::1".to_owned()));
+    }
+
+    #[pg_test]
+    fn test_inet_equality_matches_postgres() {
+        assert_eq!(Inet("10.0.0.1".to_owned()), Inet("10.0.0.1/32".to_owned()));
+        assert_eq!(Inet("::ffff:1.2.3.4".to_owned()), Inet("::FFFF:1.2.3.4".to_owned()));
+        // equality is netmask-aware, even though the hosts are the same
+        assert_ne!(Inet("192.168.1.5".to_owned()), Inet("192.168.1.5/24".to_owned()));
+    }
+
+    #[test]
+    fn test_inet_comparison_without_backend() {
+        assert_eq!(Inet("10.0.0.1".to_owned()), Inet("10.0.0.1/32".to_owned()));
+        assert!(Inet("10.0.0.0/8".to_owned()) < Inet("10.0.0.0/16".to_owned()));
+        assert_eq!(Cidr("10.1/16".to_owned()), Cidr("10.1.0.0/16".to_owned()));
+        // invalid text sorts after every address
+        assert!(Inet("::1".to_owned()) < Inet("not an address".to_owned()));
+    }
+
+    #[pg_test]
+    fn test_cidr_ordering_matches_postgres() {
+        Spi::run(
+            "CREATE TEMP TABLE cidr_ordering (value cidr);
+             INSERT INTO cidr_ordering VALUES ('10.0.0.0/8'), ('9.0.0.0/8'), ('10.0.0.0/16'),
+                ('10.1.0.0/16'), ('0.0.0.0/0'), ('2001:db8::/32'), ('192.168.1.0/24');",
+        );
+
+        let mut values = Spi::get_one::<Vec<Cidr>>("SELECT array_agg(value) FROM cidr_ordering")
+            .expect("no cidr values");
+        values.sort();
+        let ordered =
+            Spi::get_one::<Vec<Cidr>>("SELECT array_agg(value ORDER BY value) FROM cidr_ordering")
+                .expect("no cidr values");
+
+        assert_eq!(
+            values.iter().map(|cidr| cidr.0.as_str()).collect::<Vec<_>>(),
+            ordered.iter().map(|cidr| cidr.0.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(Cidr("10.1/16".to_owned()), Cidr("10.1.0.0/16".to_owned()));
+        assert!(Cidr("10.0.0.0/8".to_owned()) < Cidr("10.0.0.0/16".to_owned()));
+    }
+
+    #[pg_test]
+    fn test_arbitrary_inet_and_cidr() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        // deterministic, but varied, input bytes
+        let bytes = (0..64 * 1024u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8);
+        let bytes = bytes.collect::<Vec<_>>();
+        let mut u = Unstructured::new(&bytes);
+
+        for _ in 0..1000 {
+            // converting to a Datum panics if Postgres rejects the value
+            let inet = Inet::arbitrary(&mut u).expect("ran out of bytes");
+            let datum = Inet(inet.0.clone()).into_datum().expect("inet datum is NULL");
+            assert_eq!(unsafe { Inet::from_datum(datum, false) }, Some(inet));
+
+            let cidr = Cidr::arbitrary(&mut u).expect("ran out of bytes");
+            let datum = Cidr(cidr.0.clone()).into_datum().expect("cidr datum is NULL");
+            assert_eq!(unsafe { Cidr::from_datum(datum, false) }, Some(cidr));
+        }
+    }
+
+    #[pg_extern]
+    fn take_and_return_cidr(cidr: Cidr) -> Cidr {
+        cidr
+    }
+
+    #[pg_test]
+    fn test_take_and_return_cidr() {
+        let rc = Spi::get_one::<bool>(
+            "SELECT tests.take_and_return_cidr('10.1.0.0/16') = '10.1.0.0/16'::cidr;",
+        )
+        .expect("failed to get SPI result");
+        assert!(rc)
+    }
+
+    #[pg_extern]
+    fn take_and_return_optional_inet(inet: Option<Inet>) -> Option<Inet> {
+        inet
+    }
+
+    #[pg_test]
+    fn test_optional_inet_is_nullable_inet() {
+        use pgx::utils::sql_entity_graph::metadata::{Returns, SqlMapping, SqlTranslatable};
+
+        assert_eq!(
+            Option::<Inet>::return_sql().unwrap(),
+            Returns::One(SqlMapping::literal("inet"))
+        );
+
+        let rc = Spi::get_one::<bool>(
+            "SELECT tests.take_and_return_optional_inet(NULL) IS NULL \
+                AND tests.take_and_return_optional_inet('192.168.0.1') = '192.168.0.1'::inet;",
+        )
+        .expect("failed to get SPI result");
+        assert!(rc)
+    }
+
+    #[pg_test]
+    fn test_result_inet_is_inet() {
+        use pgx::utils::sql_entity_graph::metadata::{Returns, SqlMapping, SqlTranslatable};
+
+        assert_eq!(
+            Result::<Inet, std::net::AddrParseError>::return_sql().unwrap(),
+            Returns::One(SqlMapping::literal("inet"))
+        );
+    }
+
+    #[pg_extern]
+    fn take_and_return_ip_addr(addr: std::net::IpAddr) -> std::net::IpAddr {
+        addr
+    }
+
+    #[pg_test]
+    fn test_take_and_return_ip_addr() {
+        let rc = Spi::get_one::<bool>(
+            "SELECT tests.take_and_return_ip_addr('192.168.0.1') = '192.168.0.1'::inet \
+                AND tests.take_and_return_ip_addr('2001:db8::1/128') = '2001:db8::1'::inet;",
+        )
+        .expect("failed to get SPI result");
+        assert!(rc)
+    }
+
+    #[pg_test]
+    fn test_ip_addr_into_datum() {
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+        for addr in [IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3)), IpAddr::V6(Ipv6Addr::LOCALHOST)] {
+            let datum = addr.into_datum();
+            assert_eq!(unsafe { IpAddr::from_datum(datum.unwrap(), false) }, Some(addr));
+        }
+        assert!(unsafe { IpAddr::from_datum(pg_sys::Datum::from(0usize), true) }.is_none());
+    }
+
+    #[pg_test(error = "inet `192.168.0.0/24` has a netmask, so can't be represented as an IpAddr")]
+    fn test_ip_addr_from_network() {
+        Spi::get_one::<std::net::IpAddr>("SELECT '192.168.0.0/24'::inet");
+    }
+
+    #[pg_test(error = "inet `2001:db8::/64` has a netmask, so can't be represented as an IpAddr")]
+    fn test_ip_addr_argument_with_netmask() {
+        Spi::get_one::<std::net::IpAddr>("SELECT tests.take_and_return_ip_addr('2001:db8::/64')");
+    }
 }
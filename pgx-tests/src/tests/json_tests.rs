@@ -14,7 +14,7 @@ mod tests {
     use crate as pgx_tests;
 
     use pgx::prelude::*;
-    use pgx::{Json, JsonB};
+    use pgx::{IntoDatum, Json, JsonB};
 
     #[pg_test]
     fn test_json() {
@@ -61,4 +61,62 @@ mod tests {
         assert_eq!(user.first_name, "Blah");
         assert_eq!(user.last_name, "McBlahFace");
     }
+
+    #[pg_test]
+    fn test_jsonb_containment() {
+        let jsonb = JsonB(serde_json::json!({"a": 1, "b": [1, 2, 3], "c": {"d": true}}));
+
+        assert!(jsonb.contains(&JsonB(serde_json::json!({"a": 1}))));
+        assert!(jsonb.contains(&JsonB(serde_json::json!({"b": [3, 1]}))));
+        assert!(!jsonb.contains(&JsonB(serde_json::json!({"a": 2}))));
+
+        assert!(JsonB(serde_json::json!({"c": {"d": true}})).contained_by(&jsonb));
+        assert!(!JsonB(serde_json::json!({"z": null})).contained_by(&jsonb));
+    }
+
+    #[pg_test]
+    fn test_jsonb_has_key() {
+        let jsonb = JsonB(serde_json::json!({"a": 1, "b": 2}));
+
+        assert!(jsonb.has_key("a"));
+        assert!(!jsonb.has_key("z"));
+        assert!(jsonb.has_any_key(&["z", "b"]));
+        assert!(!jsonb.has_any_key(&["y", "z"]));
+        assert!(jsonb.has_all_keys(&["a", "b"]));
+        assert!(!jsonb.has_all_keys(&["a", "z"]));
+    }
+
+    #[pg_test]
+    fn test_jsonb_into_datum() {
+        let values = [
+            serde_json::json!(null),
+            serde_json::json!(true),
+            serde_json::json!(-42),
+            serde_json::json!(18446744073709551615u64),
+            serde_json::json!(1.5e300),
+            serde_json::json!("héllo \"wörld\""),
+            serde_json::json!([]),
+            serde_json::json!({}),
+            serde_json::json!([1, "two", [3.5, null], {"four": false}]),
+            serde_json::json!({"b": {"c": [1, 2, {"d": "e"}]}, "a": null, "": 0}),
+        ];
+
+        for value in values {
+            let text = serde_json::to_string(&value).unwrap();
+            let result = Spi::get_one_with_args::<bool>(
+                "SELECT $1 = $2::jsonb AND $1::text = ($2::jsonb)::text",
+                vec![
+                    (PgBuiltInOids::JSONBOID.oid(), JsonB(value).into_datum()),
+                    (PgBuiltInOids::TEXTOID.oid(), text.as_str().into_datum()),
+                ],
+            )
+            .expect("failed to get SPI result");
+            assert!(result, "{} did not round trip", text);
+        }
+    }
+
+    #[pg_test(error = "unsupported Unicode escape sequence: \\u0000 cannot be converted to text")]
+    fn test_jsonb_into_datum_nul() {
+        JsonB(serde_json::json!({"a": "nul\u{0}"})).into_datum();
+    }
 }
@@ -14,6 +14,7 @@ mod attributes_tests;
 mod bgworker_tests;
 mod bytea_tests;
 mod cfg_tests;
+mod compat_tests;
 mod datetime_tests;
 mod default_arg_value_tests;
 mod derive_pgtype_lifetimes;
@@ -29,16 +30,20 @@ mod lifetime_tests;
 mod log_tests;
 mod memcxt_tests;
 mod name_tests;
+mod notify_tests;
 mod numeric_tests;
+mod pg_char_tests;
 mod pg_extern_tests;
 mod pg_try_tests;
 mod pgbox_tests;
 mod postgres_type_tests;
+mod rel_tests;
 mod schema_tests;
 mod spi_tests;
 mod srf_tests;
 mod struct_type_tests;
 mod trigger_tests;
+mod tupdesc_tests;
 mod uuid_tests;
 mod variadic_tests;
 mod xact_callback_tests;
@@ -0,0 +1,29 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgx::pg_schema]
+mod tests {
+    #[allow(unused_imports)]
+    use crate as pgx_tests;
+
+    use pgx::notify::pg_notify;
+    use pgx::prelude::*;
+
+    #[pg_test]
+    fn test_pg_notify() {
+        pg_notify("pgx_notify_test", "some payload");
+        pg_notify("pgx_notify_test", "");
+    }
+
+    #[pg_test(error = "channel name cannot be empty")]
+    fn test_pg_notify_empty_channel() {
+        pg_notify("", "some payload");
+    }
+}
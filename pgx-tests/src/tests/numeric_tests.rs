@@ -14,7 +14,7 @@ mod tests {
     use crate as pgx_tests;
 
     use pgx::prelude::*;
-    use pgx::Numeric;
+    use pgx::{FixedNumeric, IntoDatum, Numeric};
     use serde::Deserialize;
 
     #[pg_extern]
@@ -32,6 +32,16 @@ mod tests {
         std::u64::MAX.into()
     }
 
+    #[pg_extern]
+    fn return_a_fixed_numeric(value: &str) -> FixedNumeric<5, 2> {
+        FixedNumeric(value.into())
+    }
+
+    #[pg_extern]
+    fn take_a_fixed_numeric(value: FixedNumeric<5, 2>) -> Numeric {
+        value.into()
+    }
+
     #[pg_test]
     fn test_return_an_i32_numeric() {
         let result = Spi::get_one::<bool>("SELECT 32::numeric = tests.return_an_i32_numeric();")
@@ -56,6 +66,58 @@ mod tests {
         assert!(result);
     }
 
+    #[pg_test]
+    fn test_numeric_arithmetic() {
+        let n = |s: &str| Numeric(s.to_string());
+
+        assert_eq!((n("0.1") + n("0.2")).0, "0.3");
+        assert_eq!((n("1.50") - n("0.25")).0, "1.25");
+        assert_eq!((n("1.5") * n("1.5")).0, "2.25");
+        // the same scale as `SELECT 1::numeric / 3`
+        assert_eq!((n("1") / n("3")).0, "0.33333333333333333333");
+        assert_eq!((n("10.5") % n("3")).0, "1.5");
+        assert_eq!((-n("42.0")).0, "-42.0");
+    }
+
+    #[pg_test(error = "division by zero")]
+    fn test_numeric_division_by_zero() {
+        let _ = Numeric("1".into()) / Numeric("0".into());
+    }
+
+    #[pg_test]
+    fn test_numeric_ordering() {
+        let n = |s: &str| Numeric(s.to_string());
+
+        assert_eq!(n("1.0"), n("1.00"));
+        assert!(n("9") < n("10"));
+        assert!(n("-1") < n("0.5"));
+        assert_eq!(n("NaN"), n("NaN"));
+        assert!(n("NaN") > n("99999999999999999999"));
+
+        let mut values = vec![n("10"), n("NaN"), n("-2.5"), n("9.99")];
+        values.sort();
+        assert_eq!(
+            values.iter().map(|v| v.0.as_str()).collect::<Vec<_>>(),
+            vec!["-2.5", "9.99", "10", "NaN"]
+        );
+    }
+
+    #[test]
+    fn test_numeric_comparison_without_backend() {
+        let n = |s: &str| Numeric(s.to_string());
+
+        assert_eq!(n("1.0"), n("1.00"));
+        assert_eq!(n("-0"), n("0.000"));
+        assert_eq!(n("1.5e2"), n("150"));
+        assert_eq!(n(" .5"), n("0.50 "));
+        assert!(n("-10") < n("-9.99"));
+        assert!(n("-Infinity") < n("-1e1000"));
+        assert!(n("1e1000") < n("inf"));
+        assert!(n("Infinity") < n("NaN"));
+        // invalid text sorts after every numeric
+        assert!(n("NaN") < n("not a number"));
+    }
+
     #[pg_test]
     fn test_deserialize_numeric() {
         use serde_json::json;
@@ -67,4 +129,71 @@ mod tests {
         let error = Numeric::deserialize(&json!("foo")).err().unwrap().to_string();
         assert_eq!("invalid Numeric value: foo", &error);
     }
+
+    #[pg_test]
+    fn test_numeric_to_from_decimal() {
+        use rust_decimal::Decimal;
+        use std::str::FromStr;
+
+        let numeric = Spi::get_one::<Numeric>("SELECT 1234567890123456789.012345678::numeric")
+            .expect("failed to get SPI result");
+        let decimal = Decimal::try_from(numeric).expect("failed to convert Numeric to Decimal");
+        assert_eq!(decimal, Decimal::from_str("1234567890123456789.012345678").unwrap());
+
+        let numeric = Numeric::from(decimal);
+        let result = Spi::get_one_with_args::<bool>(
+            "SELECT $1 = 1234567890123456789.012345678::numeric",
+            vec![(PgBuiltInOids::NUMERICOID.oid(), numeric.into_datum())],
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+
+        let nan =
+            Spi::get_one::<Numeric>("SELECT 'NaN'::numeric").expect("failed to get SPI result");
+        assert!(Decimal::try_from(nan).is_err());
+    }
+
+    #[pg_test]
+    fn test_numeric_too_precise_for_decimal() {
+        use rust_decimal::Decimal;
+
+        // 30 significant digits, but a Decimal holds at most 28
+        let numeric = Spi::get_one::<Numeric>("SELECT 12345678901234567890.0123456789::numeric")
+            .expect("failed to get SPI result");
+        assert!(Decimal::try_from(numeric).is_err());
+    }
+
+    #[pg_test]
+    fn test_fixed_numeric_rounds_to_scale() {
+        let result = Spi::get_one::<bool>(
+            "SELECT tests.return_a_fixed_numeric('123.456') = 123.46::numeric;",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+
+        let result =
+            Spi::get_one::<bool>("SELECT tests.take_a_fixed_numeric(1.005) = 1.01::numeric;")
+                .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_test(error = "numeric field overflow")]
+    fn test_fixed_numeric_out_of_range_return() {
+        Spi::get_one::<Numeric>("SELECT tests.return_a_fixed_numeric('1234.5');");
+    }
+
+    #[pg_test(error = "numeric field overflow")]
+    fn test_fixed_numeric_out_of_range_argument() {
+        Spi::get_one::<Numeric>("SELECT tests.take_a_fixed_numeric(1234.5);");
+    }
+
+    #[pg_test]
+    fn test_fixed_numeric_typmod() {
+        let result = Spi::get_one_with_args::<String>(
+            "SELECT format_type('numeric'::regtype, $1)",
+            vec![(PgBuiltInOids::INT4OID.oid(), FixedNumeric::<10, 2>::typmod().into_datum())],
+        )
+        .expect("failed to get SPI result");
+        assert_eq!(result, "numeric(10,2)");
+    }
 }
@@ -0,0 +1,59 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgx::pg_schema]
+mod tests {
+    #[allow(unused_imports)]
+    use crate as pgx_tests;
+
+    use pgx::prelude::*;
+    use pgx::PgChar;
+
+    #[pg_extern]
+    fn take_and_return_pg_char(c: PgChar) -> PgChar {
+        c
+    }
+
+    #[pg_extern]
+    fn next_pg_char(c: PgChar) -> PgChar {
+        PgChar(c.0.wrapping_add(1))
+    }
+
+    #[pg_test]
+    fn test_pg_char_roundtrip() {
+        let result = Spi::get_one::<bool>(
+            "SELECT tests.take_and_return_pg_char('a'::\"char\") = 'a'::\"char\";",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_test]
+    fn test_pg_char_high_byte() {
+        // bytes above 127 are where signed and unsigned `char` disagree
+        let result = Spi::get_one::<PgChar>("SELECT tests.next_pg_char(127::\"char\");")
+            .expect("failed to get SPI result");
+        assert_eq!(result, PgChar(128));
+
+        let result =
+            Spi::get_one::<bool>("SELECT tests.next_pg_char(127::\"char\") = (-128)::\"char\";")
+                .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_test]
+    fn test_pg_char_sql_type() {
+        let result = Spi::get_one::<String>(
+            "SELECT prorettype::regtype::text FROM pg_proc WHERE proname = 'take_and_return_pg_char';",
+        )
+        .expect("failed to get SPI result");
+        assert_eq!(result, "\"char\"");
+    }
+}
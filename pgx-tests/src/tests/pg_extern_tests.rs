@@ -106,6 +106,20 @@ mod tests {
         assert_eq!(create_result, 42);
     }
 
+    #[allow(clippy::unused_unit)]
+    #[pg_extern]
+    fn explicit_unit_return() -> () {}
+
+    #[pg_test]
+    fn test_explicit_unit_return() {
+        let result = Spi::get_one::<bool>(
+            "SELECT prorettype = 'void'::regtype FROM pg_proc WHERE proname = 'explicit_unit_return'",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+        Spi::run(r#"SELECT tests."explicit_unit_return"()"#);
+    }
+
     #[pg_extern]
     fn anyele_type(x: pgx::AnyElement) -> i32 {
         x.oid() as i32
@@ -118,4 +132,88 @@ mod tests {
                 .expect("failed to get SPI result");
         assert_eq!(interval_type as u32, pg_sys::INTERVALOID);
     }
+
+    #[pg_extern]
+    fn guarded_panic() {
+        panic!("panic in a guarded function");
+    }
+
+    // `no_guard` functions must never panic, as there's nothing to catch the unwind before it
+    // crosses the FFI boundary, so this one only checks that such functions can be called
+    #[pg_extern(no_guard)]
+    fn unguarded_add_one(i: i32) -> i32 {
+        i + 1
+    }
+
+    #[pg_test(error = "panic in a guarded function")]
+    fn test_guarded_panic_is_error() {
+        Spi::get_one::<()>(r#"SELECT tests."guarded_panic"()"#);
+    }
+
+    #[pg_test]
+    fn test_no_guard() {
+        let result = Spi::get_one::<i32>(r#"SELECT tests."unguarded_add_one"(41)"#)
+            .expect("failed to get SPI result");
+        assert_eq!(result, 42);
+    }
+
+    #[pg_test]
+    fn test_guarded_panic_is_catchable() {
+        // a panic in a guarded function surfaces as a Postgres ERROR which, like any other ERROR,
+        // can be caught by a plpgsql exception block -- the backend survives to report it
+        let result = Spi::get_one::<bool>(
+            r#"
+            DO $$
+            DECLARE
+                caught bool := false;
+            BEGIN
+                BEGIN
+                    PERFORM tests."guarded_panic"();
+                EXCEPTION WHEN OTHERS THEN
+                    caught := true;
+                END;
+                IF NOT caught THEN
+                    RAISE EXCEPTION 'guarded_panic() did not raise an ERROR';
+                END IF;
+            END
+            $$;
+            SELECT true;
+            "#,
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_extern]
+    fn inferred_strict(i: i32) -> i32 {
+        i
+    }
+
+    #[pg_extern(called_on_null_input)]
+    fn not_inferred_strict(i: i32) -> i32 {
+        i
+    }
+
+    #[pg_test]
+    fn test_strict_inference() {
+        let result = Spi::get_one::<bool>(
+            "SELECT proisstrict FROM pg_proc WHERE proname = 'inferred_strict'",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+
+        let result = Spi::get_one::<i32>(r#"SELECT tests."inferred_strict"(NULL)"#);
+        assert_eq!(result, None);
+
+        let result = Spi::get_one::<bool>(
+            "SELECT NOT proisstrict FROM pg_proc WHERE proname = 'not_inferred_strict'",
+        )
+        .expect("failed to get SPI result");
+        assert!(result);
+    }
+
+    #[pg_test(error = "i_ is null")]
+    fn test_called_on_null_input() {
+        Spi::get_one::<i32>(r#"SELECT tests."not_inferred_strict"(NULL)"#);
+    }
 }
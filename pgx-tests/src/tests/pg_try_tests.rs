@@ -37,8 +37,9 @@ mod tests {
     #[allow(unused_imports)]
     use crate as pgx_tests;
 
-    use pgx::pg_try;
     use pgx::prelude::*;
+    use pgx::{direct_function_call, pg_try, FromDatum, Inet, IntoDatum, PgSqlErrorCode};
+    use std::sync::atomic::{AtomicBool, Ordering};
 
     #[pg_test(error = "panic in walker")]
     fn test_panic_in_extern_c_fn() {
@@ -102,4 +103,101 @@ mod tests {
     fn test_pg_try_unwrap_or_rethrow_with_error_in_rethrow() {
         pg_try(|| panic!("rethrow a panic")).unwrap_or_rethrow(|| panic!("panic in rethrow"));
     }
+
+    #[pg_test]
+    fn test_pg_try_sqlerrcode() {
+        let result = pg_try(|| Inet("not an inet".into()).into_datum());
+        assert_eq!(
+            result.sqlerrcode(),
+            Some(PgSqlErrorCode::ERRCODE_INVALID_TEXT_REPRESENTATION.into())
+        );
+        unsafe { result.unwrap_or(None) };
+
+        let result = pg_try(|| panic!("not an ERROR"));
+        assert_eq!(result.sqlerrcode(), None);
+        unsafe { result.unwrap_or(()) };
+
+        let result = pg_try(|| 42);
+        assert_eq!(result.sqlerrcode(), None);
+        assert_eq!(42, result.unwrap());
+    }
+
+    #[pg_test]
+    fn test_pg_try_unwrap_or_else_sqlerrcode_with_matching_error() {
+        let result = unsafe {
+            pg_try(|| Inet("not an inet".into()).into_datum().is_some()).unwrap_or_else_sqlerrcode(
+                &[PgSqlErrorCode::ERRCODE_INVALID_TEXT_REPRESENTATION],
+                || false,
+            )
+        };
+        assert!(!result);
+    }
+
+    #[pg_test(error = "division by zero")]
+    fn test_pg_try_unwrap_or_else_sqlerrcode_with_other_error() {
+        unsafe {
+            pg_try(|| {
+                direct_function_call::<i32>(
+                    pg_sys::int4div,
+                    vec![1i32.into_datum(), 0i32.into_datum()],
+                )
+            })
+            .unwrap_or_else_sqlerrcode(
+                &[PgSqlErrorCode::ERRCODE_INVALID_TEXT_REPRESENTATION],
+                || None,
+            );
+        }
+    }
+
+    /// Sets its flag when dropped, to show whether a Rust frame was unwound or skipped
+    struct SetOnDrop<'a>(&'a AtomicBool);
+
+    impl Drop for SetOnDrop<'_> {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[pg_test]
+    fn test_error_in_datum_conversion_runs_destructors() {
+        // `inet_in` raises an ERROR partway through `Inet::into_datum()`.  Every `pg_sys` function
+        // is guarded, so the ERROR's longjmp stops at that boundary and becomes a Rust panic,
+        // which unwinds through (and drops) the Rust frames above it, rather than skipping them
+        let dropped = AtomicBool::new(false);
+        let converted = unsafe {
+            pg_try(|| {
+                let _guard = SetOnDrop(&dropped);
+                Inet("not an inet".into()).into_datum().is_some()
+            })
+            .unwrap_or_else_sqlerrcode(
+                &[PgSqlErrorCode::ERRCODE_INVALID_TEXT_REPRESENTATION],
+                || false,
+            )
+        };
+        assert!(!converted);
+        assert!(dropped.load(Ordering::SeqCst));
+
+        // and the backend is still in a state to convert valid values
+        let inet = Inet("192.168.0.1".into());
+        let datum = inet.into_datum().expect("inet datum was NULL");
+        let roundtrip = unsafe { Inet::from_datum(datum, false) }.expect("inet was NULL");
+        assert_eq!(&*roundtrip, "192.168.0.1");
+    }
+
+    #[pg_test]
+    fn test_error_in_direct_function_call_runs_destructors() {
+        let dropped = AtomicBool::new(false);
+        let result = pg_try(|| {
+            let _guard = SetOnDrop(&dropped);
+            unsafe {
+                direct_function_call::<i32>(
+                    pg_sys::int4div,
+                    vec![1i32.into_datum(), 0i32.into_datum()],
+                )
+            }
+        });
+        assert_eq!(result.sqlerrcode(), Some(PgSqlErrorCode::ERRCODE_DIVISION_BY_ZERO.into()));
+        unsafe { result.unwrap_or(None) };
+        assert!(dropped.load(Ordering::SeqCst));
+    }
 }
@@ -0,0 +1,77 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgx::pg_schema]
+mod tests {
+    #[allow(unused_imports)]
+    use crate as pgx_tests;
+
+    use pgx::prelude::*;
+    use pgx::{IntoDatum, PgRelation};
+
+    #[pg_test]
+    fn test_relation_insert() {
+        Spi::run("CREATE TABLE tests.rel_insert (id int PRIMARY KEY, name text, age int)");
+        Spi::run("CREATE INDEX ON tests.rel_insert (name)");
+
+        let relation =
+            PgRelation::with_lock(relation_oid("tests.rel_insert"), pg_sys::RowExclusiveLock as _);
+        unsafe {
+            relation.insert(&[("id", 1i32.into_datum()), ("name", "Brandy".into_datum())]);
+            relation.insert(&[("id", 2i32.into_datum()), ("age", 3i32.into_datum())]);
+        }
+
+        let brandy =
+            Spi::get_one::<bool>("SELECT age IS NULL FROM tests.rel_insert WHERE name = 'Brandy'");
+        assert_eq!(brandy, Some(true));
+
+        // the rows must be findable through the indices, too
+        Spi::run("SET LOCAL enable_seqscan TO off");
+        let count = Spi::get_one::<i64>(
+            "SELECT count(*) FROM tests.rel_insert WHERE id = 2 OR name = 'Brandy'",
+        );
+        assert_eq!(count, Some(2));
+    }
+
+    #[pg_test(error = "duplicate key value violates unique constraint \"rel_insert_unique_pkey\"")]
+    fn test_relation_insert_unique() {
+        Spi::run("CREATE TABLE tests.rel_insert_unique (id int PRIMARY KEY)");
+
+        let relation = PgRelation::with_lock(
+            relation_oid("tests.rel_insert_unique"),
+            pg_sys::RowExclusiveLock as _,
+        );
+        unsafe {
+            relation.insert(&[("id", 1i32.into_datum())]);
+            relation.insert(&[("id", 1i32.into_datum())]);
+        }
+    }
+
+    #[pg_test(error = "column `nope` of relation `rel_insert_unknown` does not exist")]
+    fn test_relation_insert_unknown_column() {
+        Spi::run("CREATE TABLE tests.rel_insert_unknown (id int)");
+
+        let relation = PgRelation::with_lock(
+            relation_oid("tests.rel_insert_unknown"),
+            pg_sys::RowExclusiveLock as _,
+        );
+        unsafe {
+            relation.insert(&[("nope", 1i32.into_datum())]);
+        }
+    }
+
+    fn relation_oid(name: &str) -> pg_sys::Oid {
+        Spi::get_one_with_args::<pg_sys::Oid>(
+            "SELECT $1::regclass::oid",
+            vec![(PgBuiltInOids::TEXTOID.oid(), name.into_datum())],
+        )
+        .expect("relation does not exist")
+    }
+}
@@ -38,6 +38,10 @@ mod test_schema {
     #[pgx(sql = "CREATE TYPE test_schema.ManuallyRenderedType;")]
     pub struct OverriddenType(pub u64);
 
+    #[derive(Debug, PostgresType, Serialize, Deserialize)]
+    #[pgx(generates = "CREATE CAST (test_schema.TypeWithGeneratedCast AS json) WITH INOUT;")]
+    pub struct TypeWithGeneratedCast(pub u64);
+
     fn generate_function(
         entity: &SqlGraphEntity,
         _context: &PgxSql,
@@ -163,4 +167,21 @@ mod tests {
         .expect("expected result");
         assert_eq!(result, true);
     }
+
+    #[pg_test]
+    fn generated_sql_is_appended() {
+        // Validate that the type was still created by the default SQL
+        let result: bool = Spi::get_one(
+            "SELECT exists(SELECT 1 FROM pg_type WHERE typname = 'typewithgeneratedcast');",
+        )
+        .expect("expected result");
+        assert_eq!(result, true);
+
+        // and that the cast was created after it
+        let result: bool = Spi::get_one(
+            "SELECT exists(SELECT 1 FROM pg_cast WHERE castsource = 'test_schema.typewithgeneratedcast'::regtype AND casttarget = 'json'::regtype);",
+        )
+        .expect("expected result");
+        assert_eq!(result, true);
+    }
 }
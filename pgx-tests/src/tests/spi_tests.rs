@@ -165,6 +165,88 @@ mod tests {
         assert!(result.0.get(0).unwrap().get("Plan").is_some());
     }
 
+    #[pg_test]
+    fn test_spi_query_builder() {
+        let sum = Spi::query("SELECT $1 + $2").bind(40i32).bind(2i64).get_one::<i64>();
+        assert_eq!(sum, Some(42));
+
+        let (a, b) =
+            Spi::query("SELECT $1, $2").bind("hello").bind(None::<i32>).get_two::<String, i32>();
+        assert_eq!(a, Some("hello".to_string()));
+        assert_eq!(b, None);
+
+        let values = Spi::query("SELECT generate_series(1, $1)").bind(3i32).get::<i32>();
+        assert_eq!(values, vec![Some(1), Some(2), Some(3)]);
+
+        Spi::query("SELECT $1 = 1").bind(1i32).run();
+    }
+
+    #[pg_test]
+    fn test_spi_query_builder_iter() {
+        Spi::execute(|client| {
+            let names = Spi::query("SELECT relname::text FROM pg_class WHERE relname = $1")
+                .bind("pg_class")
+                .iter(&client)
+                .map(|row| row.by_name("relname").unwrap().value::<String>())
+                .collect::<Vec<_>>();
+            assert_eq!(names, vec![Some("pg_class".to_string())]);
+        });
+    }
+
+    #[pg_test]
+    fn test_spi_get_by_name() {
+        use pgx::{Inet, SpiError, SpiTupleError};
+
+        Spi::execute(|client| {
+            let row = client
+                .select("SELECT '10.0.0.1'::inet AS addr, NULL::int4 AS n", None, None)
+                .first()
+                .get_heap_tuple()
+                .unwrap();
+
+            assert_eq!(row.get_by_name::<Inet>("addr"), Ok(Some(Inet("10.0.0.1".to_string()))));
+            assert_eq!(row.get_by_ordinal::<Inet>(1), Ok(Some(Inet("10.0.0.1".to_string()))));
+            assert_eq!(row.get_by_name::<i32>("n"), Ok(None));
+            assert_eq!(
+                row.get_by_name::<i32>("missing"),
+                Err(SpiTupleError::Spi(SpiError::NoAttribute))
+            );
+            assert_eq!(
+                row.get_by_ordinal::<i32>(3),
+                Err(SpiTupleError::Spi(SpiError::NoAttribute))
+            );
+            assert_eq!(
+                row.get_by_name::<i32>("addr"),
+                Err(SpiTupleError::TypeMismatch {
+                    expected: pg_sys::INT4OID,
+                    found: pg_sys::INETOID
+                })
+            );
+        });
+    }
+
+    #[pg_test]
+    fn test_spi_cursor() {
+        Spi::execute(|client| {
+            let mut cursor = client.open_cursor(
+                "SELECT * FROM generate_series(1, $1)",
+                Some(vec![(PgBuiltInOids::INT4OID.oid(), pgx::IntoDatum::into_datum(10))]),
+            );
+            let mut batches = vec![];
+            loop {
+                let batch = cursor
+                    .fetch(3)
+                    .map(|row| row.by_ordinal(1).unwrap().value::<i32>().unwrap())
+                    .collect::<Vec<_>>();
+                if batch.is_empty() {
+                    break;
+                }
+                batches.push(batch);
+            }
+            assert_eq!(batches, vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9], vec![10]]);
+        });
+    }
+
     #[pg_extern]
     fn do_panic() {
         panic!("did a panic");
@@ -0,0 +1,63 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+#[cfg(any(test, feature = "pg_test"))]
+#[pgx::pg_schema]
+mod tests {
+    #[allow(unused_imports)]
+    use crate as pgx_tests;
+
+    use pgx::prelude::*;
+    use pgx::{PgOid, PgRelation, PgTupleDesc};
+
+    #[pg_test]
+    fn test_tupdesc_from_relation() {
+        Spi::run("CREATE TABLE tests.tupdesc_rel (id int NOT NULL, name varchar(10))");
+        let oid = Spi::get_one::<pg_sys::Oid>("SELECT 'tests.tupdesc_rel'::regclass::oid")
+            .expect("relation does not exist");
+
+        let relation = PgRelation::with_lock(oid, pg_sys::AccessShareLock as _);
+        let tupdesc = PgTupleDesc::from_relation(&relation);
+        assert_eq!(tupdesc.len(), 2);
+
+        let id = tupdesc.get(0).expect("no attribute 0");
+        assert_eq!(id.name(), "id");
+        assert_eq!(id.type_oid(), PgOid::BuiltIn(PgBuiltInOids::INT4OID));
+        assert_eq!(id.type_mod(), -1);
+        assert!(id.is_not_null());
+
+        let name = tupdesc.get(1).expect("no attribute 1");
+        assert_eq!(name.name(), "name");
+        assert_eq!(name.type_oid(), PgOid::BuiltIn(PgBuiltInOids::VARCHAROID));
+        // varchar's typmod includes the 4-byte varlena header
+        assert_eq!(name.type_mod(), 14);
+        assert!(!name.is_not_null());
+
+        assert!(tupdesc.get(2).is_none());
+    }
+
+    #[pg_test]
+    fn test_tupdesc_iter_composite_type() {
+        Spi::run("CREATE TYPE tests.tupdesc_dog AS (name text, age int, weight float8)");
+        let tupdesc = PgTupleDesc::for_composite_type("tests.tupdesc_dog").expect("no such type");
+
+        let names = tupdesc.iter().map(|att| att.name().to_string()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["name", "age", "weight"]);
+
+        let types = tupdesc.iter().map(|att| att.type_oid()).collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                PgOid::BuiltIn(PgBuiltInOids::TEXTOID),
+                PgOid::BuiltIn(PgBuiltInOids::INT4OID),
+                PgOid::BuiltIn(PgBuiltInOids::FLOAT8OID),
+            ]
+        );
+    }
+}
@@ -32,6 +32,7 @@ pub enum ExternArgs {
     CreateOrReplace,
     Immutable,
     Strict,
+    CalledOnNullInput,
     Stable,
     Volatile,
     Raw,
@@ -52,6 +53,7 @@ impl core::fmt::Display for ExternArgs {
             ExternArgs::CreateOrReplace => write!(f, "CREATE OR REPLACE"),
             ExternArgs::Immutable => write!(f, "IMMUTABLE"),
             ExternArgs::Strict => write!(f, "STRICT"),
+            ExternArgs::CalledOnNullInput => write!(f, "CALLED ON NULL INPUT"),
             ExternArgs::Stable => write!(f, "STABLE"),
             ExternArgs::Volatile => write!(f, "VOLATILE"),
             ExternArgs::Raw => Ok(()),
@@ -74,6 +76,7 @@ impl ToTokens for ExternArgs {
             ExternArgs::CreateOrReplace => tokens.append(format_ident!("CreateOrReplace")),
             ExternArgs::Immutable => tokens.append(format_ident!("Immutable")),
             ExternArgs::Strict => tokens.append(format_ident!("Strict")),
+            ExternArgs::CalledOnNullInput => tokens.append(format_ident!("CalledOnNullInput")),
             ExternArgs::Stable => tokens.append(format_ident!("Stable")),
             ExternArgs::Volatile => tokens.append(format_ident!("Volatile")),
             ExternArgs::Raw => tokens.append(format_ident!("Raw")),
@@ -138,13 +141,17 @@ pub enum CategorizedType {
     Default,
 }
 
-pub fn parse_extern_attributes(attr: TokenStream) -> HashSet<ExternArgs> {
+/// Parse the arguments of a `#[pg_extern]`-style attribute.
+///
+/// Errors if more than one of `parallel_safe` (or just `parallel`), `parallel_unsafe`, and
+/// `parallel_restricted` is given
+pub fn parse_extern_attributes(attr: TokenStream) -> Result<HashSet<ExternArgs>, syn::Error> {
     let mut args = HashSet::<ExternArgs>::new();
     let mut itr = attr.into_iter();
     while let Some(t) = itr.next() {
         match t {
             TokenTree::Group(g) => {
-                for arg in parse_extern_attributes(g.stream()).into_iter() {
+                for arg in parse_extern_attributes(g.stream())?.into_iter() {
                     args.insert(arg);
                 }
             }
@@ -154,11 +161,12 @@ pub fn parse_extern_attributes(attr: TokenStream) -> HashSet<ExternArgs> {
                     "create_or_replace" => args.insert(ExternArgs::CreateOrReplace),
                     "immutable" => args.insert(ExternArgs::Immutable),
                     "strict" => args.insert(ExternArgs::Strict),
+                    "called_on_null_input" => args.insert(ExternArgs::CalledOnNullInput),
                     "stable" => args.insert(ExternArgs::Stable),
                     "volatile" => args.insert(ExternArgs::Volatile),
                     "raw" => args.insert(ExternArgs::Raw),
                     "no_guard" => args.insert(ExternArgs::NoGuard),
-                    "parallel_safe" => args.insert(ExternArgs::ParallelSafe),
+                    "parallel" | "parallel_safe" => args.insert(ExternArgs::ParallelSafe),
                     "parallel_unsafe" => args.insert(ExternArgs::ParallelUnsafe),
                     "parallel_restricted" => args.insert(ExternArgs::ParallelRestricted),
                     "error" => {
@@ -204,7 +212,20 @@ pub fn parse_extern_attributes(attr: TokenStream) -> HashSet<ExternArgs> {
             TokenTree::Literal(_) => {}
         }
     }
-    args
+
+    let parallel =
+        [ExternArgs::ParallelSafe, ExternArgs::ParallelUnsafe, ExternArgs::ParallelRestricted]
+            .into_iter()
+            .filter(|arg| args.contains(arg))
+            .count();
+    if parallel > 1 {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "`parallel_safe`, `parallel_unsafe`, and `parallel_restricted` are mutually exclusive",
+        ));
+    }
+
+    Ok(args)
 }
 
 pub fn categorize_type(ty: &Type) -> CategorizedType {
@@ -542,7 +563,27 @@ mod tests {
         let s = "error = \"syntax error at or near \\\"THIS\\\"\"";
         let ts = proc_macro2::TokenStream::from_str(s).unwrap();
 
-        let args = parse_extern_attributes(ts);
+        let args = parse_extern_attributes(ts).unwrap();
         assert!(args.contains(&ExternArgs::Error("syntax error at or near \"THIS\"".to_string())));
     }
+
+    #[test]
+    fn parse_plain_parallel() {
+        let ts = proc_macro2::TokenStream::from_str("immutable, parallel").unwrap();
+
+        let args = parse_extern_attributes(ts).unwrap();
+        assert!(args.contains(&ExternArgs::ParallelSafe));
+        assert!(args.contains(&ExternArgs::Immutable));
+    }
+
+    #[test]
+    fn parse_conflicting_parallel() {
+        for s in ["parallel_safe, parallel_unsafe", "parallel, parallel_restricted"] {
+            let ts = proc_macro2::TokenStream::from_str(s).unwrap();
+            assert!(parse_extern_attributes(ts).is_err(), "{s}");
+        }
+
+        let ts = proc_macro2::TokenStream::from_str("parallel, parallel_safe").unwrap();
+        assert!(parse_extern_attributes(ts).is_ok());
+    }
 }
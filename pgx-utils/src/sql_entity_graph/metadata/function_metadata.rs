@@ -65,17 +65,6 @@ where
     }
 }
 
-impl FunctionMetadata<(), ()> for fn() {
-    fn entity(&self) -> FunctionMetadataEntity {
-        FunctionMetadataEntity { arguments: vec![], retval: None, path: self.path() }
-    }
-}
-
-impl FunctionMetadata<(), ()> for unsafe fn() {
-    fn entity(&self) -> FunctionMetadataEntity {
-        FunctionMetadataEntity { arguments: vec![], retval: None, path: self.path() }
-    }
-}
 seq_macro::seq!(I in 0..=32 {
     #(
         seq_macro::seq!(N in 0..=I {
@@ -130,50 +119,6 @@ seq_macro::seq!(I in 0..=32 {
                     }
                 }
             }
-
-            impl<#(Input~N,)*> FunctionMetadata<(#(Input~N,)*), ()> for fn(#(Input~N,)*)
-            where
-                #(
-                    Input~N: SqlTranslatable,
-                )*
-            {
-                fn entity(&self) -> FunctionMetadataEntity {
-                    let mut arguments = Vec::new();
-                    #(
-                        arguments.push({
-                            let marker: PhantomData<Input~N> = PhantomData;
-                            marker.entity()
-                        });
-                    )*
-                    FunctionMetadataEntity {
-                        arguments,
-                        retval: None,
-                        path: self.path(),
-                    }
-                }
-            }
-
-            impl<#(Input~N,)*> FunctionMetadata<(#(Input~N,)*), ()> for unsafe fn(#(Input~N,)*)
-            where
-                #(
-                    Input~N: SqlTranslatable,
-                )*
-            {
-                fn entity(&self) -> FunctionMetadataEntity {
-                    let mut arguments = Vec::new();
-                    #(
-                        arguments.push({
-                            let marker: PhantomData<Input~N> = PhantomData;
-                            marker.entity()
-                        });
-                    )*
-                    FunctionMetadataEntity {
-                        arguments,
-                        retval: None,
-                        path: self.path(),
-                    }
-                }
-            }
         });
     )*
 });
@@ -58,7 +58,7 @@ impl std::fmt::Display for ReturnsError {
                 write!(f, "SqlMapping::Skip inside Array is not valid")
             }
             ReturnsError::BareU8 => {
-                write!(f, "Cannot use bare u8")
+                write!(f, "Cannot use bare u8, use `pgx::PgChar` for Postgres' `\"char\"` type")
             }
             ReturnsError::Datum => {
                 write!(
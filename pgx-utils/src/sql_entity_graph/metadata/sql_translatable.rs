@@ -18,6 +18,7 @@ pub enum ArgumentError {
     BareU8,
     SkipInArray,
     Datum,
+    Void,
 }
 
 impl std::fmt::Display for ArgumentError {
@@ -30,7 +31,7 @@ impl std::fmt::Display for ArgumentError {
                 write!(f, "Cannot use TableIterator as an argument")
             }
             ArgumentError::BareU8 => {
-                write!(f, "Cannot use bare u8")
+                write!(f, "Cannot use bare u8, use `pgx::PgChar` for Postgres' `\"char\"` type")
             }
             ArgumentError::SkipInArray => {
                 write!(f, "SqlMapping::Skip inside Array is not valid")
@@ -38,6 +39,12 @@ impl std::fmt::Display for ArgumentError {
             ArgumentError::Datum => {
                 write!(f, "A Datum as an argument means that `sql = \"...\"` must be set in the declaration")
             }
+            ArgumentError::Void => {
+                write!(
+                    f,
+                    "Cannot use () as an argument, Postgres' `void` is only valid as a return type"
+                )
+            }
         }
     }
 }
@@ -62,6 +69,63 @@ impl SqlMapping {
     pub fn literal(s: &'static str) -> SqlMapping {
         SqlMapping::As(String::from(s))
     }
+
+    /// Does this look like a type reference Postgres could parse?
+    ///
+    /// This is only a lightweight, syntactic check which catches typos such as unbalanced quotes
+    /// or brackets, and stray punctuation, when the schema is generated rather than when the
+    /// extension is created.  Mappings other than [`SqlMapping::As`] are always plausible.
+    pub fn is_plausible(&self) -> bool {
+        match self {
+            SqlMapping::As(sql) => is_plausible_type_name(sql),
+            _ => true,
+        }
+    }
+}
+
+/// Allows (possibly schema-qualified and quoted) identifiers, multi-word names such as
+/// `double precision`, type modifiers such as `numeric(10, 2)`, and array brackets
+fn is_plausible_type_name(sql: &str) -> bool {
+    let mut chars = sql.trim().chars().peekable();
+    let mut saw_identifier = false;
+    let mut parens = 0;
+    let mut brackets = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                // a quoted identifier, in which `""` is an escaped quote
+                let mut len = 0;
+                loop {
+                    match chars.next() {
+                        Some('"') if chars.peek() == Some(&'"') => {
+                            chars.next();
+                        }
+                        Some('"') => break,
+                        Some(_) => (),
+                        None => return false,
+                    }
+                    len += 1;
+                }
+                if len == 0 {
+                    return false;
+                }
+                saw_identifier = true;
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                while chars.next_if(|&c| c.is_alphanumeric() || c == '_' || c == '$').is_some() {}
+                saw_identifier = true;
+            }
+            c if c.is_ascii_digit() || c.is_whitespace() || c == '.' || c == ',' => (),
+            '(' => parens += 1,
+            '[' => brackets += 1,
+            ')' if parens > 0 => parens -= 1,
+            ']' if brackets > 0 => brackets -= 1,
+            _ => return false,
+        }
+    }
+
+    saw_identifier && parens == 0 && brackets == 0
 }
 
 impl Error for ArgumentError {}
@@ -102,6 +166,15 @@ pub unsafe trait SqlTranslatable {
     }
 }
 
+unsafe impl SqlTranslatable for () {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Err(ArgumentError::Void)
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("void")))
+    }
+}
+
 unsafe impl<T> SqlTranslatable for Option<T>
 where
     T: SqlTranslatable,
@@ -132,10 +205,12 @@ where
     }
 }
 
+/// An `Err` is raised as an ERROR, so the SQL type is `T`'s.  `E` need only be displayable, so
+/// that a boxed `dyn Error`, which doesn't itself implement `Error`, also works
 unsafe impl<T, E> SqlTranslatable for Result<T, E>
 where
     T: SqlTranslatable,
-    E: std::error::Error + 'static,
+    E: std::fmt::Display + 'static,
 {
     fn argument_sql() -> Result<SqlMapping, ArgumentError> {
         T::argument_sql()
@@ -357,3 +432,103 @@ unsafe impl SqlTranslatable for cstr_core::CStr {
         Ok(Returns::One(SqlMapping::literal("cstring")))
     }
 }
+
+unsafe impl SqlTranslatable for std::time::SystemTime {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("timestamp with time zone"))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("timestamp with time zone")))
+    }
+}
+
+unsafe impl SqlTranslatable for std::time::Duration {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("interval"))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("interval")))
+    }
+}
+
+unsafe impl SqlTranslatable for std::net::IpAddr {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("inet"))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("inet")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ArgumentError, Returns, SqlMapping, SqlTranslatable};
+
+    #[test]
+    fn plausible_type_names() {
+        for sql in [
+            "void",
+            "TEXT",
+            "double precision",
+            "timestamp with time zone",
+            "numeric(10, 2)[]",
+            "\"char\"",
+            "myschema.\"My \"\"Type\"\"\"[][]",
+        ] {
+            assert!(SqlMapping::As(sql.to_string()).is_plausible(), "{sql}");
+        }
+        assert!(SqlMapping::Skip.is_plausible());
+    }
+
+    #[test]
+    fn implausible_type_names() {
+        for sql in ["", "  ", "int;", "text]", "in'et", "(int", "\"\"", "\"unclosed", "int -- c"] {
+            assert!(!SqlMapping::As(sql.to_string()).is_plausible(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn option_is_nullable_inner_type() {
+        assert_eq!(Option::<i32>::argument_sql().unwrap(), SqlMapping::literal("INT"));
+        assert_eq!(Option::<i32>::return_sql().unwrap(), Returns::One(SqlMapping::literal("INT")));
+        assert!(Option::<i32>::optional());
+        assert!(!i32::optional());
+    }
+
+    #[test]
+    fn option_composes_with_arrays() {
+        assert_eq!(Vec::<Option<i32>>::argument_sql().unwrap(), SqlMapping::literal("INT[]"));
+        assert_eq!(
+            Vec::<Option<i32>>::return_sql().unwrap(),
+            Returns::One(SqlMapping::literal("INT[]"))
+        );
+        assert_eq!(
+            Option::<Vec<Option<i32>>>::return_sql().unwrap(),
+            Returns::One(SqlMapping::literal("INT[]"))
+        );
+    }
+
+    #[test]
+    fn result_is_ok_type() {
+        type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+
+        assert_eq!(
+            Result::<i32, std::io::Error>::return_sql().unwrap(),
+            Returns::One(SqlMapping::literal("INT"))
+        );
+        assert_eq!(
+            Result::<Vec<i32>, BoxedError>::return_sql().unwrap(),
+            Returns::One(SqlMapping::literal("INT[]"))
+        );
+        assert_eq!(
+            Result::<Option<i32>, BoxedError>::argument_sql().unwrap(),
+            SqlMapping::literal("INT")
+        );
+    }
+
+    #[test]
+    fn unit_is_void_only_as_a_return_type() {
+        assert_eq!(<()>::return_sql().unwrap(), Returns::One(SqlMapping::literal("void")));
+        assert_eq!(<()>::argument_sql(), Err(ArgumentError::Void));
+    }
+}
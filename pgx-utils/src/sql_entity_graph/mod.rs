@@ -48,7 +48,7 @@ pub use pg_extern::{NameMacro, PgExtern, PgExternArgument, PgOperator};
 pub use pg_trigger::attribute::PgTriggerAttribute;
 pub use pg_trigger::entity::PgTriggerEntity;
 pub use pg_trigger::PgTrigger;
-pub use pgx_sql::{PgxSql, RustToSqlMapping};
+pub use pgx_sql::{wrap_in_transaction, PgxSql, RustToSqlMapping, SqlGenerationError};
 pub use positioning_ref::PositioningRef;
 pub use postgres_enum::entity::PostgresEnumEntity;
 pub use postgres_enum::PostgresEnum;
@@ -189,6 +189,29 @@ impl SqlGraphIdentifier for SqlGraphEntity {
 impl ToSql for SqlGraphEntity {
     #[tracing::instrument(level = "debug", skip(self, context), fields(identifier = %self.rust_identifier()))]
     fn to_sql(&self, context: &PgxSql) -> eyre::Result<String> {
+        let sql = self.item_sql(context)?;
+        Ok(match self.to_sql_config() {
+            Some(config) => config.append_generated(sql, context),
+            None => sql,
+        })
+    }
+}
+
+impl SqlGraphEntity {
+    fn to_sql_config(&self) -> Option<&ToSqlConfigEntity> {
+        match self {
+            SqlGraphEntity::Function(item) => Some(&item.to_sql_config),
+            SqlGraphEntity::Type(item) => Some(&item.to_sql_config),
+            SqlGraphEntity::Enum(item) => Some(&item.to_sql_config),
+            SqlGraphEntity::Ord(item) => Some(&item.to_sql_config),
+            SqlGraphEntity::Hash(item) => Some(&item.to_sql_config),
+            SqlGraphEntity::Aggregate(item) => Some(&item.to_sql_config),
+            SqlGraphEntity::Trigger(item) => Some(&item.to_sql_config),
+            _ => None,
+        }
+    }
+
+    fn item_sql(&self, context: &PgxSql) -> eyre::Result<String> {
         match self {
             SqlGraphEntity::Schema(item) => {
                 if item.name != "public" && item.name != "pg_catalog" {
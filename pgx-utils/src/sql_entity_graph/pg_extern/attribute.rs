@@ -26,6 +26,7 @@ use syn::Token;
 pub enum Attribute {
     Immutable,
     Strict,
+    CalledOnNullInput,
     Stable,
     Volatile,
     Raw,
@@ -47,6 +48,9 @@ impl Attribute {
         match self {
             Attribute::Immutable => quote! { ::pgx::utils::ExternArgs::Immutable },
             Attribute::Strict => quote! { ::pgx::utils::ExternArgs::Strict },
+            Attribute::CalledOnNullInput => {
+                quote! { ::pgx::utils::ExternArgs::CalledOnNullInput }
+            }
             Attribute::Stable => quote! { ::pgx::utils::ExternArgs::Stable },
             Attribute::Volatile => quote! { ::pgx::utils::ExternArgs::Volatile },
             Attribute::Raw => quote! { ::pgx::utils::ExternArgs::Raw },
@@ -90,6 +94,7 @@ impl ToTokens for Attribute {
         let quoted = match self {
             Attribute::Immutable => quote! { immutable },
             Attribute::Strict => quote! { strict },
+            Attribute::CalledOnNullInput => quote! { called_on_null_input },
             Attribute::Stable => quote! { stable },
             Attribute::Volatile => quote! { volatile },
             Attribute::Raw => quote! { raw },
@@ -135,12 +140,13 @@ impl Parse for Attribute {
         let found = match ident.to_string().as_str() {
             "immutable" => Self::Immutable,
             "strict" => Self::Strict,
+            "called_on_null_input" => Self::CalledOnNullInput,
             "stable" => Self::Stable,
             "volatile" => Self::Volatile,
             "raw" => Self::Raw,
             "no_guard" => Self::NoGuard,
             "create_or_replace" => Self::CreateOrReplace,
-            "parallel_safe" => Self::ParallelSafe,
+            "parallel" | "parallel_safe" => Self::ParallelSafe,
             "parallel_unsafe" => Self::ParallelUnsafe,
             "parallel_restricted" => Self::ParallelRestricted,
             "error" => {
@@ -109,9 +109,12 @@ impl ToSql for PgExternEntity {
     fn to_sql(&self, context: &PgxSql) -> eyre::Result<String> {
         let self_index = context.externs[self];
         let mut extern_attrs = self.extern_attrs.clone();
-        // if we already have a STRICT marker we do not need to add it
+        // if we already have a STRICT marker we do not need to add it, and if the user has asked
+        // to be called on NULL input we must not add it
         // presume we can upgrade, then disprove it
-        let mut strict_upgrade = !extern_attrs.iter().any(|i| i == &ExternArgs::Strict);
+        let mut strict_upgrade = !extern_attrs
+            .iter()
+            .any(|i| i == &ExternArgs::Strict || i == &ExternArgs::CalledOnNullInput);
         if strict_upgrade {
             // It may be possible to infer a `STRICT` marker though.
             // But we can only do that if the user hasn't used `Option<T>` or `pgx::Internal`
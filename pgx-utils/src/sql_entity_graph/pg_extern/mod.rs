@@ -92,6 +92,25 @@ impl PgExtern {
 
         let mut to_sql_config = to_sql_config.unwrap_or_default();
 
+        if attrs.contains(&Attribute::Strict) && attrs.contains(&Attribute::CalledOnNullInput) {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`strict` and `called_on_null_input` are mutually exclusive",
+            ));
+        }
+
+        let parallel =
+            [Attribute::ParallelSafe, Attribute::ParallelUnsafe, Attribute::ParallelRestricted]
+                .iter()
+                .filter(|parallel| attrs.contains(parallel))
+                .count();
+        if parallel > 1 {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "`parallel_safe`, `parallel_unsafe`, and `parallel_restricted` are mutually exclusive",
+            ));
+        }
+
         let func = syn::parse2::<syn::ItemFn>(item)?;
 
         if let Some(ref mut content) = to_sql_config.content {
@@ -297,6 +297,10 @@ impl TryFrom<&syn::ReturnType> for Returning {
                         let used_ty = UsedType::new(syn::Type::Reference(ty_ref.clone()))?;
                         Ok(Returning::Type(used_ty))
                     }
+                    // an explicit `-> ()` is the same as no return type at all
+                    syn::Type::Tuple(ref type_tuple) if type_tuple.elems.is_empty() => {
+                        Ok(Returning::None)
+                    }
                     syn::Type::Macro(ref mut type_macro) => Self::parse_type_macro(type_macro),
                     syn::Type::Paren(ref mut type_paren) => match &mut *type_paren.elem {
                         syn::Type::Macro(ref mut type_macro) => Self::parse_type_macro(type_macro),
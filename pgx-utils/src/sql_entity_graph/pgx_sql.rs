@@ -30,6 +30,7 @@ use crate::sql_entity_graph::control_file::ControlFile;
 use crate::sql_entity_graph::extension_sql::entity::{ExtensionSqlEntity, SqlDeclaredEntity};
 use crate::sql_entity_graph::extension_sql::SqlDeclared;
 use crate::sql_entity_graph::mapping::RustSourceOnlySqlMapping;
+use crate::sql_entity_graph::metadata::{Returns, SqlMapping};
 use crate::sql_entity_graph::pg_extern::entity::PgExternEntity;
 use crate::sql_entity_graph::pg_trigger::entity::PgTriggerEntity;
 use crate::sql_entity_graph::positioning_ref::PositioningRef;
@@ -55,6 +56,26 @@ pub struct RustToSqlMapping {
     pub rust_source_to_sql: std::collections::HashSet<RustSourceOnlySqlMapping>,
 }
 
+/// An entity which [`PgxSql::to_sql_keep_going`] could not translate to SQL.
+#[derive(Debug)]
+pub struct SqlGenerationError {
+    pub identifier: String,
+    pub file: Option<&'static str>,
+    pub line: Option<u32>,
+    pub error: eyre::Report,
+}
+
+impl std::fmt::Display for SqlGenerationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.file, self.line) {
+            (Some(file), Some(line)) => write!(f, "{}:{}: ", file, line)?,
+            (Some(file), None) => write!(f, "{}: ", file)?,
+            _ => (),
+        }
+        write!(f, "{}: {:#}", self.identifier, self.error)
+    }
+}
+
 /// A generator for SQL.
 ///
 /// Consumes a base mapping of types (typically `pgx::DEFAULT_TYPEID_SQL_MAPPING`), a
@@ -86,6 +107,10 @@ pub struct PgxSql {
     pub triggers: HashMap<PgTriggerEntity, NodeIndex>,
     pub extension_name: String,
     pub versioned_so: bool,
+    /// Don't qualify objects outside a `#[pg_schema]` module with the extension's schema, so
+    /// they're created in whatever schema is first in the `search_path`.  `#[pg_schema]` modules
+    /// are still created and qualified as usual
+    pub no_default_schema: bool,
 }
 
 impl PgxSql {
@@ -149,6 +174,8 @@ impl PgxSql {
             }
         }
 
+        validate_sql_mappings(&externs)?;
+
         let control: ControlFile = control.expect("No control file found");
         let root = graph.add_node(SqlGraphEntity::ExtensionRoot(control.clone()));
 
@@ -258,6 +285,7 @@ impl PgxSql {
             graph_finalize: finalize,
             extension_name: extension_name,
             versioned_so,
+            no_default_schema: false,
         };
         Ok(this)
     }
@@ -412,6 +440,7 @@ impl PgxSql {
             .neighbors_undirected(*item_index)
             .flat_map(|neighbor_index| match &self.graph[neighbor_index] {
                 SqlGraphEntity::Schema(s) => Some(String::from(s.name)),
+                SqlGraphEntity::ExtensionRoot(_) if self.no_default_schema => None,
                 SqlGraphEntity::ExtensionRoot(control) => {
                     if !control.relocatable {
                         control.schema.clone()
@@ -433,9 +462,7 @@ impl PgxSql {
     #[instrument(level = "error", skip(self))]
     pub fn to_sql(&self) -> eyre::Result<String> {
         let mut full_sql = String::new();
-        for step_id in petgraph::algo::toposort(&self.graph, None).map_err(|e| {
-            eyre!("Failed to toposort SQL entities, node with cycle: {:?}", self.graph[e.node_id()])
-        })? {
+        for step_id in self.toposort()? {
             let step = &self.graph[step_id];
 
             let sql = step.to_sql(self)?;
@@ -448,6 +475,39 @@ impl PgxSql {
         Ok(full_sql)
     }
 
+    /// Like [`PgxSql::to_sql`], but rather than stopping at the first entity which fails to
+    /// translate, carries on and returns every failure alongside the SQL of the entities which
+    /// did translate.
+    #[instrument(level = "error", skip(self))]
+    pub fn to_sql_keep_going(&self) -> eyre::Result<(String, Vec<SqlGenerationError>)> {
+        let mut full_sql = String::new();
+        let mut errors = Vec::new();
+        for step_id in self.toposort()? {
+            let step = &self.graph[step_id];
+
+            match step.to_sql(self) {
+                Ok(sql) if !sql.is_empty() => {
+                    full_sql.push_str(&sql);
+                    full_sql.push('\n');
+                }
+                Ok(_) => (),
+                Err(error) => errors.push(SqlGenerationError {
+                    identifier: step.rust_identifier(),
+                    file: step.file(),
+                    line: step.line(),
+                    error,
+                }),
+            }
+        }
+        Ok((full_sql, errors))
+    }
+
+    fn toposort(&self) -> eyre::Result<Vec<NodeIndex>> {
+        petgraph::algo::toposort(&self.graph, None).map_err(|e| {
+            eyre!("Failed to toposort SQL entities, node with cycle: {:?}", self.graph[e.node_id()])
+        })
+    }
+
     pub fn has_sql_declared_entity(&self, identifier: &SqlDeclared) -> Option<&SqlDeclaredEntity> {
         self.extension_sqls.iter().find_map(|(item, _index)| {
             let retval = item.creates.iter().find_map(|create_entity| {
@@ -1586,3 +1646,342 @@ fn make_type_or_enum_connection(
 
     found
 }
+
+/// Wrap generated SQL in `BEGIN;` and `COMMIT;`, so that if any statement fails, none apply.
+///
+/// Statements which Postgres refuses to run inside a transaction block, such as
+/// `CREATE INDEX CONCURRENTLY`, are moved after the `COMMIT;`, in their original order.
+///
+/// This isn't for extension install scripts, as `CREATE EXTENSION` already runs those in a
+/// transaction, and rejects transaction control statements.
+pub fn wrap_in_transaction(sql: &str) -> String {
+    let mut transactional = String::new();
+    let mut non_transactional = String::new();
+    for statement in split_statements(sql) {
+        if runs_outside_transaction(statement) {
+            non_transactional.push_str(statement.trim());
+            non_transactional.push('\n');
+        } else {
+            transactional.push_str(statement);
+        }
+    }
+
+    let mut wrapped = format!("BEGIN;\n{}\nCOMMIT;\n", transactional.trim_end());
+    if !non_transactional.is_empty() {
+        wrapped.push_str("\n-- these statements can't run inside a transaction block\n");
+        wrapped.push_str(&non_transactional);
+    }
+    wrapped
+}
+
+/// Split SQL into its statements, each including its terminating `;`, by finding the `;`s which
+/// aren't in a string, quoted identifier, dollar-quoted body, or comment
+fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => {
+                // `E'...'` strings allow backslash escapes, so long as the `E` isn't the end of
+                // some longer word, such as `LIKE'...'`
+                let escapes = i > 0
+                    && matches!(bytes[i - 1], b'E' | b'e')
+                    && (i < 2 || !is_identifier_byte(bytes[i - 2]));
+                i += 1;
+                while i < bytes.len() {
+                    match bytes[i] {
+                        b'\\' if escapes => i += 1,
+                        b'\'' if bytes.get(i + 1) == Some(&b'\'') => i += 1,
+                        b'\'' => break,
+                        _ => {}
+                    }
+                    i += 1;
+                }
+            }
+            b'"' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != b'"' {
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                // block comments nest
+                let mut depth = 0;
+                while i < bytes.len() {
+                    if bytes[i..].starts_with(b"/*") {
+                        depth += 1;
+                        i += 1;
+                    } else if bytes[i..].starts_with(b"*/") {
+                        depth -= 1;
+                        i += 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+            }
+            // a `$` within an identifier, such as `a$b$`, doesn't start a dollar quote
+            b'$' if i == 0 || !is_identifier_byte(bytes[i - 1]) => {
+                if let Some(tag) = dollar_quote_tag(&sql[i..]) {
+                    let body_start = i + tag.len();
+                    i = match sql[body_start..].find(tag) {
+                        Some(end) => body_start + end + tag.len() - 1,
+                        None => bytes.len(),
+                    };
+                }
+            }
+            b';' => {
+                statements.push(&sql[start..=i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if start < sql.len() {
+        statements.push(&sql[start..]);
+    }
+    statements
+}
+
+/// Can this byte be part of an unquoted identifier or keyword?  Non-ASCII bytes are, as Postgres
+/// allows any non-ASCII letter
+fn is_identifier_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || !byte.is_ascii()
+}
+
+/// The `$tag$` (or `$$`) starting `sql`, if it starts a dollar-quoted string
+fn dollar_quote_tag(sql: &str) -> Option<&str> {
+    let end = sql[1..].find('$')? + 1;
+    let tag = &sql[1..end];
+    let mut chars = tag.chars();
+    let valid = match chars.next() {
+        None => true,
+        Some(first) => {
+            (first.is_alphabetic() || first == '_')
+                && chars.all(|c| c.is_alphanumeric() || c == '_')
+        }
+    };
+    valid.then(|| &sql[..=end])
+}
+
+/// Does Postgres refuse to run this statement inside a transaction block?
+fn runs_outside_transaction(statement: &str) -> bool {
+    // skip any comments preceding the statement
+    let mut rest = statement;
+    loop {
+        rest = rest.trim_start();
+        if rest.starts_with("--") {
+            rest = rest.find('\n').map(|end| &rest[end..]).unwrap_or("");
+        } else if rest.starts_with("/*") {
+            rest = rest.find("*/").map(|end| &rest[end + 2..]).unwrap_or("");
+        } else {
+            break;
+        }
+    }
+
+    let words = rest
+        .split(|c: char| c.is_whitespace() || c == ';')
+        .filter(|word| !word.is_empty())
+        .take(6)
+        .map(|word| word.to_uppercase())
+        .collect::<Vec<_>>();
+    let word = |n: usize| words.get(n).map(String::as_str).unwrap_or("");
+    let concurrently = words.iter().any(|word| word == "CONCURRENTLY");
+
+    match (word(0), word(1), word(2)) {
+        ("VACUUM", _, _) => true,
+        ("CREATE" | "DROP", "DATABASE" | "TABLESPACE", _) => true,
+        ("ALTER", "SYSTEM", _) => true,
+        ("CREATE" | "DROP", "INDEX", _) | ("CREATE", "UNIQUE", "INDEX") | ("REINDEX", _, _) => {
+            concurrently
+        }
+        _ => false,
+    }
+}
+
+/// Reject any function whose argument or return types map to something which doesn't look like a
+/// SQL type, so the mistake surfaces now rather than at `CREATE EXTENSION` time
+fn validate_sql_mappings(externs: &[PgExternEntity]) -> eyre::Result<()> {
+    for item in externs {
+        for ty in item.metadata.arguments.iter().chain(item.metadata.retval.iter()) {
+            let mut mappings = Vec::new();
+            if let Ok(mapping) = &ty.argument_sql {
+                mappings.push(mapping);
+            }
+            match &ty.return_sql {
+                Ok(Returns::One(mapping)) | Ok(Returns::SetOf(mapping)) => mappings.push(mapping),
+                Ok(Returns::Table(table)) => mappings.extend(table),
+                Err(_) => (),
+            }
+            if let Some(SqlMapping::As(sql)) = mappings.into_iter().find(|m| !m.is_plausible()) {
+                return Err(eyre!(
+                    "`{}` maps `{}` to `{}`, which isn't a valid SQL type name",
+                    item.full_path,
+                    ty.type_name,
+                    sql
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_statements, wrap_in_transaction, PgxSql, RustToSqlMapping};
+    use crate::sql_entity_graph::metadata::{FunctionMetadata, Returns, SqlMapping};
+    use crate::sql_entity_graph::{
+        ControlFile, PgExternEntity, PgExternReturnEntity, SchemaEntity, SqlGraphEntity,
+        ToSqlConfigEntity,
+    };
+
+    /// A `#[pg_extern] fn` with no arguments or return value, declared in `module_path`, with the
+    /// metadata the macro would give it
+    fn function(
+        name: &'static str,
+        module_path: &'static str,
+        full_path: &'static str,
+    ) -> SqlGraphEntity {
+        SqlGraphEntity::Function(PgExternEntity {
+            name,
+            unaliased_name: name,
+            module_path,
+            full_path,
+            metadata: FunctionMetadata::entity(&(unit as fn())),
+            fn_args: vec![],
+            fn_return: PgExternReturnEntity::None,
+            schema: None,
+            file: "src/lib.rs",
+            line: 1,
+            extern_attrs: vec![],
+            search_path: None,
+            operator: None,
+            to_sql_config: ToSqlConfigEntity {
+                enabled: true,
+                callback: None,
+                content: None,
+                generates: None,
+            },
+        })
+    }
+
+    fn unit() {}
+
+    /// Generate the schema of an extension installed into `my_schema`, with a function at its
+    /// root and another in a `#[pg_schema] mod other`
+    fn generate_schema(no_default_schema: bool) -> String {
+        let entities = vec![
+            SqlGraphEntity::ExtensionRoot(ControlFile {
+                comment: String::new(),
+                default_version: String::from("1.0"),
+                module_pathname: None,
+                relocatable: false,
+                superuser: false,
+                schema: Some(String::from("my_schema")),
+            }),
+            SqlGraphEntity::Schema(SchemaEntity {
+                module_path: "my_ext::other",
+                name: "other",
+                file: "src/lib.rs",
+                line: 1,
+            }),
+            function("at_root", "my_ext", "my_ext::at_root"),
+            function("in_schema", "my_ext::other", "my_ext::other::in_schema"),
+        ];
+        let mappings = RustToSqlMapping { rust_source_to_sql: Default::default() };
+        let mut pgx_sql =
+            PgxSql::build(mappings, entities.into_iter(), String::from("my_ext"), false).unwrap();
+        pgx_sql.no_default_schema = no_default_schema;
+        pgx_sql.to_sql().unwrap()
+    }
+
+    #[test]
+    fn schema_qualifies_objects() {
+        let sql = generate_schema(false);
+        assert!(sql.contains("CREATE SCHEMA IF NOT EXISTS other;"));
+        assert!(sql.contains("FUNCTION my_schema.\"at_root\"()"));
+        assert!(sql.contains("FUNCTION other.\"in_schema\"()"));
+    }
+
+    #[test]
+    fn unit_returning_function_returns_void() {
+        let metadata = FunctionMetadata::entity(&(unit as fn()));
+        assert_eq!(
+            metadata.retval.unwrap().return_sql,
+            Ok(Returns::One(SqlMapping::literal("void")))
+        );
+
+        let sql = generate_schema(false);
+        assert!(sql.contains("FUNCTION my_schema.\"at_root\"() RETURNS void\n"));
+    }
+
+    #[test]
+    fn no_default_schema_only_unqualifies_the_extension_root() {
+        let sql = generate_schema(true);
+        assert!(sql.contains("CREATE SCHEMA IF NOT EXISTS other;"));
+        assert!(sql.contains("FUNCTION \"at_root\"()"));
+        assert!(sql.contains("FUNCTION other.\"in_schema\"()"));
+    }
+
+    #[test]
+    fn split_statements_ignores_quoted_semicolons() {
+        let sql = "SELECT ';';\nSELECT \"a;b\" FROM t; -- c;d\nCREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql;\nSELECT E'\\';' /* ; /* ; */ ; */;";
+        let statements = split_statements(sql);
+        assert_eq!(
+            statements,
+            vec![
+                "SELECT ';';",
+                "\nSELECT \"a;b\" FROM t;",
+                " -- c;d\nCREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql;",
+                "\nSELECT E'\\';' /* ; /* ; */ ; */;",
+            ]
+        );
+    }
+
+    #[test]
+    fn split_statements_ignores_dollars_within_identifiers() {
+        let sql = "SELECT a$b$ FROM t;\nSELECT $$;$$;";
+        assert_eq!(split_statements(sql), vec!["SELECT a$b$ FROM t;", "\nSELECT $$;$$;"]);
+    }
+
+    #[test]
+    fn split_statements_only_escapes_in_e_strings() {
+        let sql = "SELECT 'a' LIKE'a\\';\nSELECT e'\\';';";
+        assert_eq!(split_statements(sql), vec!["SELECT 'a' LIKE'a\\';", "\nSELECT e'\\';';"]);
+    }
+
+    #[test]
+    fn wrap_in_transaction_moves_non_transactional_statements() {
+        let sql = "CREATE TABLE t (id int);\n\
+                   -- an index\n\
+                   CREATE UNIQUE INDEX CONCURRENTLY t_id ON t (id);\n\
+                   CREATE INDEX t_id2 ON t (id);\n";
+        assert_eq!(
+            wrap_in_transaction(sql),
+            "BEGIN;\n\
+             CREATE TABLE t (id int);\n\
+             CREATE INDEX t_id2 ON t (id);\n\
+             COMMIT;\n\
+             \n\
+             -- these statements can't run inside a transaction block\n\
+             -- an index\n\
+             CREATE UNIQUE INDEX CONCURRENTLY t_id ON t (id);\n"
+        );
+    }
+
+    #[test]
+    fn wrap_in_transaction_without_non_transactional_statements() {
+        assert_eq!(wrap_in_transaction("SELECT 1;\n"), "BEGIN;\nSELECT 1;\nCOMMIT;\n");
+    }
+}
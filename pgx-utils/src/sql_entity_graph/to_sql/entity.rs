@@ -32,11 +32,14 @@ use crate::sql_entity_graph::SqlGraphEntity;
 ///
 /// When `callback` has a value, the corresponding `ToSql` implementation should invoke the
 /// callback instead of performing their default behavior.
+///
+/// When `generates` has a value, it is emitted after whatever SQL was generated for the item.
 #[derive(Default, Clone)]
 pub struct ToSqlConfigEntity {
     pub enabled: bool,
     pub callback: Option<ToSqlFn>,
     pub content: Option<&'static str>,
+    pub generates: Option<&'static str>,
 }
 impl ToSqlConfigEntity {
     /// Given a SqlGraphEntity, this function converts it to SQL based on the current configuration.
@@ -104,10 +107,22 @@ impl ToSqlConfigEntity {
 
         None
     }
+
+    /// Append the `#[pgx(generates = "...")]` SQL, if any, to the item's `sql`
+    pub fn append_generated(&self, sql: String, context: &PgxSql) -> String {
+        match self.generates {
+            Some(generates) if self.enabled => {
+                let module_pathname = context.get_module_pathname();
+                let generates = generates.replace("@MODULE_PATHNAME@", &module_pathname);
+                format!("{sql}\n{generates}\n")
+            }
+            _ => sql,
+        }
+    }
 }
 impl std::cmp::PartialEq for ToSqlConfigEntity {
     fn eq(&self, other: &Self) -> bool {
-        if self.enabled != other.enabled {
+        if self.enabled != other.enabled || self.generates != other.generates {
             return false;
         }
         match (self.callback, other.callback) {
@@ -127,6 +142,7 @@ impl std::hash::Hash for ToSqlConfigEntity {
         self.enabled.hash(state);
         self.callback.map(|cb| std::ptr::addr_of!(cb)).hash(state);
         self.content.hash(state);
+        self.generates.hash(state);
     }
 }
 impl std::fmt::Debug for ToSqlConfigEntity {
@@ -136,6 +152,7 @@ impl std::fmt::Debug for ToSqlConfigEntity {
             .field("enabled", &self.enabled)
             .field("callback", &format_args!("{:?}", &callback))
             .field("content", &self.content)
+            .field("generates", &self.generates)
             .finish()
     }
 }
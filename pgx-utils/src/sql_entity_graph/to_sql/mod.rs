@@ -56,30 +56,34 @@ pub struct ToSqlConfig {
     pub enabled: bool,
     pub callback: Option<syn::Path>,
     pub content: Option<syn::LitStr>,
+    /// Additional SQL emitted after the item's SQL, from `#[pgx(generates = "...")]`
+    pub generates: Option<syn::LitStr>,
 }
 impl From<bool> for ToSqlConfig {
     fn from(enabled: bool) -> Self {
-        Self { enabled, callback: None, content: None }
+        Self { enabled, callback: None, content: None, generates: None }
     }
 }
 impl From<syn::Path> for ToSqlConfig {
     fn from(path: syn::Path) -> Self {
-        Self { enabled: true, callback: Some(path), content: None }
+        Self { enabled: true, callback: Some(path), content: None, generates: None }
     }
 }
 impl From<syn::LitStr> for ToSqlConfig {
     fn from(content: syn::LitStr) -> Self {
-        Self { enabled: true, callback: None, content: Some(content) }
+        Self { enabled: true, callback: None, content: Some(content), generates: None }
     }
 }
 impl Default for ToSqlConfig {
     fn default() -> Self {
-        Self { enabled: true, callback: None, content: None }
+        Self { enabled: true, callback: None, content: None, generates: None }
     }
 }
 
 const INVALID_ATTR_CONTENT: &str =
     "expected `#[pgx(sql = content)]`, where `content` is a boolean, string, or path to a function";
+const INVALID_GENERATES_CONTENT: &str =
+    "expected `#[pgx(generates = content)]`, where `content` is a string of additional SQL";
 
 impl ToSqlConfig {
     /// Used for general purpose parsing from an attribute
@@ -92,38 +96,43 @@ impl ToSqlConfig {
         }
 
         let attr = attr.parse_args::<PgxAttribute>()?;
+        let mut config: Option<Self> = None;
+        let mut generates = None;
         for arg in attr.args.iter() {
             if let PgxArg::NameValue(ref nv) = arg {
-                if !nv.path.is_ident("sql") {
+                if nv.path.is_ident("generates") {
+                    match nv.value {
+                        ArgValue::Lit(Lit::Str(ref s)) => generates = Some(s.clone()),
+                        ArgValue::Lit(ref other) => {
+                            return Err(syn::Error::new(other.span(), INVALID_GENERATES_CONTENT));
+                        }
+                        ArgValue::Path(ref other) => {
+                            return Err(syn::Error::new(other.span(), INVALID_GENERATES_CONTENT));
+                        }
+                    }
+                    continue;
+                }
+                if !nv.path.is_ident("sql") || config.is_some() {
                     continue;
                 }
 
-                match nv.value {
-                    ArgValue::Path(ref callback_path) => {
-                        return Ok(Some(Self {
-                            enabled: true,
-                            callback: Some(callback_path.clone()),
-                            content: None,
-                        }));
-                    }
-                    ArgValue::Lit(Lit::Bool(ref b)) => {
-                        return Ok(Some(Self { enabled: b.value, callback: None, content: None }));
-                    }
-                    ArgValue::Lit(Lit::Str(ref s)) => {
-                        return Ok(Some(Self {
-                            enabled: true,
-                            callback: None,
-                            content: Some(s.clone()),
-                        }));
-                    }
+                config = Some(match nv.value {
+                    ArgValue::Path(ref callback_path) => Self::from(callback_path.clone()),
+                    ArgValue::Lit(Lit::Bool(ref b)) => Self::from(b.value),
+                    ArgValue::Lit(Lit::Str(ref s)) => Self::from(s.clone()),
                     ArgValue::Lit(ref other) => {
                         return Err(syn::Error::new(other.span(), INVALID_ATTR_CONTENT));
                     }
-                }
+                });
             }
         }
 
-        Ok(None)
+        if generates.is_some() {
+            // `generates` alone keeps the default SQL, and appends to it
+            config.get_or_insert_with(Self::default).generates = generates;
+        }
+
+        Ok(config)
     }
 
     /// Used to parse a generator config from a set of item attributes
@@ -143,33 +152,24 @@ impl ToSqlConfig {
 impl ToTokens for ToSqlConfig {
     fn to_tokens(&self, tokens: &mut TokenStream2) {
         let enabled = self.enabled;
-        let callback = &self.callback;
-        let content = &self.content;
-        if let Some(callback_path) = callback {
-            tokens.append_all(quote! {
-                ::pgx::utils::sql_entity_graph::ToSqlConfigEntity {
-                    enabled: #enabled,
-                    callback: Some(#callback_path),
-                    content: None,
-                }
-            });
-            return;
-        }
-        if let Some(sql) = content {
-            tokens.append_all(quote! {
-                ::pgx::utils::sql_entity_graph::ToSqlConfigEntity {
-                    enabled: #enabled,
-                    callback: None,
-                    content: Some(#sql),
-                }
-            });
-            return;
-        }
+        let callback = match &self.callback {
+            Some(callback_path) => quote! { Some(#callback_path) },
+            None => quote! { None },
+        };
+        let content = match &self.content {
+            Some(sql) => quote! { Some(#sql) },
+            None => quote! { None },
+        };
+        let generates = match &self.generates {
+            Some(sql) => quote! { Some(#sql) },
+            None => quote! { None },
+        };
         tokens.append_all(quote! {
             ::pgx::utils::sql_entity_graph::ToSqlConfigEntity {
                 enabled: #enabled,
-                callback: None,
-                content: None,
+                callback: #callback,
+                content: #content,
+                generates: #generates,
             }
         });
     }
@@ -13,6 +13,9 @@ extern "C" {
     /// Does a field access, but doesn't deref out of bounds of ArrayType
     fn pgx_ARR_DIMS(arrayType: *mut ArrayType) -> *mut libc::c_int;
     /// # Safety
+    /// Does a field access, but doesn't deref out of bounds of ArrayType
+    fn pgx_ARR_LBOUND(arrayType: *mut ArrayType) -> *mut libc::c_int;
+    /// # Safety
     /// Must only be used on a "valid" (Postgres-constructed) ArrayType
     fn pgx_ARR_NELEMS(arrayType: *mut ArrayType) -> i32;
     /// # Safety
@@ -143,6 +146,25 @@ impl RawArray {
         }
     }
 
+    /**
+    A slice of the lower bounds, the subscript of the first element in each dimension.
+
+    Oxidized form of [ARR_LBOUND(ArrayType*)][ARR_LBOUND].
+    The length will be the same as [RawArray::dims].
+
+    Safe to use because validity of this slice was asserted on construction.
+
+    [ARR_LBOUND]: <https://git.postgresql.org/gitweb/?p=postgresql.git;a=blob;f=src/include/utils/array.h;h=4ae6c3be2f8b57afa38c19af2779f67c782e4efc;hb=278273ccbad27a8834dfdf11895da9cd91de4114#l289>
+    */
+    pub fn lower_bounds(&self) -> &[libc::c_int] {
+        // SAFETY: the lower bounds directly follow the dimensions, so the same reasoning as
+        // for `RawArray::dims` applies
+        unsafe {
+            let ndim = self.ndim() as usize;
+            slice::from_raw_parts(pgx_ARR_LBOUND(self.ptr.as_ptr()), ndim)
+        }
+    }
+
     /// The flattened length of the array over every single element.
     /// Includes all items, even the ones that might be null.
     #[inline]
@@ -0,0 +1,153 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+//! Stable wrappers over `pg_sys` functions that were renamed or changed signature between
+//! Postgres major versions.
+//!
+//! Each function here has the same Rust signature regardless of which `pgXX` feature is active,
+//! so callers don't need their own `#[cfg(feature = "pgXX")]` blocks.
+use crate::pg_sys;
+
+/// Open a table relation by OID.  This is `heap_open()` on pg10/11 and `table_open()` on pg12+.
+///
+/// # Safety
+///
+/// Must be called from within a transaction.  Postgres raises an ERROR if `relid` doesn't exist
+/// or isn't a table, and the returned relation must be closed with [`table_close`] before the
+/// transaction ends
+#[inline]
+pub unsafe fn table_open(relid: pg_sys::Oid, lockmode: pg_sys::LOCKMODE) -> pg_sys::Relation {
+    #[cfg(any(feature = "pg10", feature = "pg11"))]
+    {
+        pg_sys::heap_open(relid, lockmode)
+    }
+
+    #[cfg(any(feature = "pg12", feature = "pg13", feature = "pg14"))]
+    {
+        pg_sys::table_open(relid, lockmode)
+    }
+}
+
+/// Close a relation opened with [`table_open`].  On pg10/11 `heap_close()` is a macro over
+/// `relation_close()`, and on pg12+ this is `table_close()`.
+///
+/// # Safety
+///
+/// `relation` must be a valid, open relation, and is invalid once this returns.  `lockmode`
+/// should be the one it was opened with, or `NoLock` to keep the lock until the transaction ends
+#[inline]
+pub unsafe fn table_close(relation: pg_sys::Relation, lockmode: pg_sys::LOCKMODE) {
+    #[cfg(any(feature = "pg10", feature = "pg11"))]
+    {
+        pg_sys::relation_close(relation, lockmode)
+    }
+
+    #[cfg(any(feature = "pg12", feature = "pg13", feature = "pg14"))]
+    {
+        pg_sys::table_close(relation, lockmode)
+    }
+}
+
+/// Is the attribute `attnum` (1-based) of `tuple` NULL?
+///
+/// pg11 added the `tupdesc` argument, which is used to account for attributes added after the
+/// tuple was written.  It's ignored on pg10.
+///
+/// # Safety
+///
+/// `tuple` must point to a valid heap tuple, and `tupdesc` must be a valid descriptor of it
+#[inline]
+pub unsafe fn heap_attisnull(
+    tuple: pg_sys::HeapTuple,
+    attnum: i32,
+    tupdesc: pg_sys::TupleDesc,
+) -> bool {
+    #[cfg(feature = "pg10")]
+    {
+        let _ = tupdesc;
+        pg_sys::heap_attisnull(tuple, attnum)
+    }
+
+    #[cfg(any(feature = "pg11", feature = "pg12", feature = "pg13", feature = "pg14"))]
+    {
+        pg_sys::heap_attisnull(tuple, attnum, tupdesc)
+    }
+}
+
+/// Insert an index tuple pointing at `heap_tid`.
+///
+/// pg14 added an `indexUnchanged` hint argument, which is always passed as `false` here.
+///
+/// # Safety
+///
+/// The relations must be open and suitably locked, with `index_relation` an index on
+/// `heap_relation`.  `values` and `isnull` must each point to one element per index column, and
+/// `heap_tid` and `index_info` must be valid
+#[inline]
+pub unsafe fn index_insert(
+    index_relation: pg_sys::Relation,
+    values: *mut pg_sys::Datum,
+    isnull: *mut bool,
+    heap_tid: pg_sys::ItemPointer,
+    heap_relation: pg_sys::Relation,
+    check_unique: pg_sys::IndexUniqueCheck,
+    index_info: *mut pg_sys::IndexInfo,
+) -> bool {
+    #[cfg(any(feature = "pg10", feature = "pg11", feature = "pg12", feature = "pg13"))]
+    {
+        pg_sys::index_insert(
+            index_relation,
+            values,
+            isnull,
+            heap_tid,
+            heap_relation,
+            check_unique,
+            index_info,
+        )
+    }
+
+    #[cfg(feature = "pg14")]
+    {
+        pg_sys::index_insert(
+            index_relation,
+            values,
+            isnull,
+            heap_tid,
+            heap_relation,
+            check_unique,
+            false,
+            index_info,
+        )
+    }
+}
+
+/// Returns the next transaction id to be assigned along with its epoch.
+///
+/// This is `GetNextXidAndEpoch()` on pg10/11 and `ReadNextFullTransactionId()` on pg12+.
+#[inline]
+pub fn next_xid_and_epoch() -> (pg_sys::TransactionId, u32) {
+    #[cfg(any(feature = "pg10", feature = "pg11"))]
+    {
+        let mut last_xid = pg_sys::InvalidTransactionId;
+        let mut epoch = 0u32;
+
+        unsafe {
+            pg_sys::GetNextXidAndEpoch(&mut last_xid, &mut epoch);
+        }
+
+        (last_xid, epoch)
+    }
+
+    #[cfg(any(feature = "pg12", feature = "pg13", feature = "pg14"))]
+    {
+        let full_xid = unsafe { pg_sys::ReadNextFullTransactionId() };
+
+        (full_xid.value as u32, (full_xid.value >> 32) as u32)
+    }
+}
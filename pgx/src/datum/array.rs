@@ -9,7 +9,7 @@ Use of this source code is governed by the MIT license that can be found in the
 
 use crate::array::RawArray;
 use crate::layout::*;
-use crate::{pg_sys, FromDatum, IntoDatum, PgMemoryContexts};
+use crate::{pg_sys, FromDatum, IntoDatum};
 use bitvec::slice::BitSlice;
 use core::ptr::NonNull;
 use pgx_utils::sql_entity_graph::metadata::{
@@ -298,6 +298,31 @@ impl<'a, T: FromDatum> Array<'a, T> {
         self.nelems == 0
     }
 
+    /// The number of dimensions.  An empty array has none
+    #[inline]
+    pub fn ndim(&self) -> usize {
+        self.dims().len()
+    }
+
+    /// The length of each dimension, outermost first.  The elements of a multidimensional array
+    /// are flattened, in row-major order, by [`Array::iter()`] and [`Array::get()`]
+    pub fn dims(&self) -> Vec<i32> {
+        match &self.raw {
+            Some(raw) => raw.dims().to_vec(),
+            None if self.nelems == 0 => vec![],
+            None => vec![self.nelems as i32],
+        }
+    }
+
+    /// The subscript of the first element in each dimension.  This is `1` unless the array was
+    /// made with explicit bounds, such as `'[2:4]={1,2,3}'::int[]`
+    pub fn lower_bounds(&self) -> Vec<i32> {
+        match &self.raw {
+            Some(raw) => raw.lower_bounds().to_vec(),
+            None => vec![1; self.ndim()],
+        }
+    }
+
     #[allow(clippy::option_option)]
     #[inline]
     pub fn get(&self, i: usize) -> Option<Option<T>> {
@@ -358,6 +383,19 @@ impl<'a, T: FromDatum> VariadicArray<'a, T> {
         self.0.iter_deny_null()
     }
 
+    #[inline]
+    pub fn ndim(&self) -> usize {
+        self.0.ndim()
+    }
+
+    pub fn dims(&self) -> Vec<i32> {
+        self.0.dims()
+    }
+
+    pub fn lower_bounds(&self) -> Vec<i32> {
+        self.0.lower_bounds()
+    }
+
     #[inline]
     pub fn len(&self) -> usize {
         self.0.len()
@@ -562,36 +600,7 @@ where
     T: IntoDatum,
 {
     fn into_datum(self) -> Option<pg_sys::Datum> {
-        let mut state = unsafe {
-            pg_sys::initArrayResult(
-                T::type_oid(),
-                PgMemoryContexts::CurrentMemoryContext.value(),
-                false,
-            )
-        };
-        for s in self {
-            let datum = s.into_datum();
-            let isnull = datum.is_none();
-
-            unsafe {
-                state = pg_sys::accumArrayResult(
-                    state,
-                    datum.unwrap_or(0.into()),
-                    isnull,
-                    T::type_oid(),
-                    PgMemoryContexts::CurrentMemoryContext.value(),
-                );
-            }
-        }
-
-        if state.is_null() {
-            // shoudln't happen
-            None
-        } else {
-            Some(unsafe {
-                pg_sys::makeArrayResult(state, PgMemoryContexts::CurrentMemoryContext.value())
-            })
-        }
+        Some(construct_array_datum(self.into_iter()))
     }
 
     fn type_oid() -> u32 {
@@ -609,36 +618,7 @@ where
     T: IntoDatum + Copy + 'a,
 {
     fn into_datum(self) -> Option<pg_sys::Datum> {
-        let mut state = unsafe {
-            pg_sys::initArrayResult(
-                T::type_oid(),
-                PgMemoryContexts::CurrentMemoryContext.value(),
-                false,
-            )
-        };
-        for s in self {
-            let datum = s.into_datum();
-            let isnull = datum.is_none();
-
-            unsafe {
-                state = pg_sys::accumArrayResult(
-                    state,
-                    datum.unwrap_or(0.into()),
-                    isnull,
-                    T::type_oid(),
-                    PgMemoryContexts::CurrentMemoryContext.value(),
-                );
-            }
-        }
-
-        if state.is_null() {
-            // shoudln't happen
-            None
-        } else {
-            Some(unsafe {
-                pg_sys::makeArrayResult(state, PgMemoryContexts::CurrentMemoryContext.value())
-            })
-        }
+        Some(construct_array_datum(self.iter().copied()))
     }
 
     fn type_oid() -> u32 {
@@ -651,6 +631,43 @@ where
     }
 }
 
+/// Build a one-dimensional Postgres array of `T` from `elements`, allocated in the
+/// `CurrentMemoryContext`.
+///
+/// The element type's layout is looked up once, and each element's Datum is copied just once,
+/// straight into the array, rather than also into an intermediate `ArrayBuildState`
+fn construct_array_datum<T: IntoDatum>(elements: impl Iterator<Item = T>) -> pg_sys::Datum {
+    let (lower, _) = elements.size_hint();
+    let mut datums = Vec::with_capacity(lower);
+    let mut nulls = Vec::with_capacity(lower);
+    for element in elements {
+        let datum = element.into_datum();
+        nulls.push(datum.is_none());
+        datums.push(datum.unwrap_or(0.into()));
+    }
+
+    let elem_type = T::type_oid();
+    let (mut typlen, mut typbyval, mut typalign) = Default::default();
+    // Postgres' empty arrays have no dimensions at all, rather than one of length zero
+    let ndims = if datums.is_empty() { 0 } else { 1 };
+    let mut dims = [datums.len() as i32];
+    let mut lbs = [1];
+    unsafe {
+        pg_sys::get_typlenbyvalalign(elem_type, &mut typlen, &mut typbyval, &mut typalign);
+        pg_sys::Datum::from(pg_sys::construct_md_array(
+            datums.as_mut_ptr(),
+            nulls.as_mut_ptr(),
+            ndims,
+            dims.as_mut_ptr(),
+            lbs.as_mut_ptr(),
+            elem_type,
+            typlen as i32,
+            typbyval,
+            typalign,
+        ))
+    }
+}
+
 unsafe impl<'a, T> SqlTranslatable for Array<'a, T>
 where
     T: SqlTranslatable + FromDatum,
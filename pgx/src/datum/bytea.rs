@@ -0,0 +1,130 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+use crate::{direct_function_call, pg_sys, FromDatum, IntoDatum};
+use core::fmt::Write;
+use pgx_utils::sql_entity_graph::metadata::{
+    ArgumentError, Returns, ReturnsError, SqlMapping, SqlTranslatable,
+};
+use std::ffi::CStr;
+use std::ops::Deref;
+
+/// An owned Postgres `bytea`.  This converts to and from Datums exactly as `Vec<u8>` does, but
+/// can also render itself as text
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Bytea(pub Vec<u8>);
+
+/// The textual representation to use for a [`Bytea`]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ByteaFormat {
+    /// Whichever of `hex` or `escape` the session's `bytea_output` setting asks for
+    Session,
+    /// Postgres' `hex` format, such as `\x6869`
+    Hex,
+    /// Postgres' traditional `escape` format, such as `hi\000`
+    Escape,
+}
+
+impl Bytea {
+    /// Render these bytes as Postgres would in the given `format`.
+    ///
+    /// Only [`ByteaFormat::Session`] calls into Postgres (via `byteaout`), and so must be used
+    /// from within a transaction
+    pub fn to_text(&self, format: ByteaFormat) -> String {
+        match format {
+            ByteaFormat::Session => unsafe {
+                direct_function_call::<&CStr>(
+                    pg_sys::byteaout,
+                    vec![self.0.as_slice().into_datum()],
+                )
+                .expect("byteaout returned NULL")
+                .to_str()
+                .expect("byteaout returned invalid UTF-8")
+                .to_owned()
+            },
+            ByteaFormat::Hex => self.to_hex(),
+            ByteaFormat::Escape => self.to_escape(),
+        }
+    }
+
+    /// Encode as Postgres' `hex` format, regardless of the session's `bytea_output` setting
+    pub fn to_hex(&self) -> String {
+        let mut text = String::with_capacity(2 + self.0.len() * 2);
+        text.push_str("\\x");
+        for byte in &self.0 {
+            write!(text, "{byte:02x}").unwrap();
+        }
+        text
+    }
+
+    /// Encode as Postgres' `escape` format, regardless of the session's `bytea_output` setting
+    pub fn to_escape(&self) -> String {
+        let mut text = String::with_capacity(self.0.len());
+        for &byte in &self.0 {
+            match byte {
+                b'\\' => text.push_str("\\\\"),
+                // the same range of "printable" bytes that `byteaout` leaves as-is
+                0x20..=0x7e => text.push(byte as char),
+                _ => write!(text, "\\{byte:03o}").unwrap(),
+            }
+        }
+        text
+    }
+}
+
+impl Deref for Bytea {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for Bytea {
+    fn from(bytes: Vec<u8>) -> Self {
+        Bytea(bytes)
+    }
+}
+
+impl From<Bytea> for Vec<u8> {
+    fn from(bytea: Bytea) -> Self {
+        bytea.0
+    }
+}
+
+impl FromDatum for Bytea {
+    #[inline]
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        typoid: pg_sys::Oid,
+    ) -> Option<Bytea> {
+        Vec::<u8>::from_polymorphic_datum(datum, is_null, typoid).map(Bytea)
+    }
+}
+
+impl IntoDatum for Bytea {
+    #[inline]
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.0.into_datum()
+    }
+
+    #[inline]
+    fn type_oid() -> u32 {
+        pg_sys::BYTEAOID
+    }
+}
+
+unsafe impl SqlTranslatable for Bytea {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("bytea"))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("bytea")))
+    }
+}
@@ -0,0 +1,215 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use super::inet::network_serde;
+use crate::{
+    direct_function_call, direct_function_call_as_datum, pg_sys, pg_try, FromDatum, Inet,
+    IntoDatum, PgSqlErrorCode,
+};
+use pgx_utils::sql_entity_graph::metadata::{
+    ArgumentError, Returns, ReturnsError, SqlMapping, SqlTranslatable,
+};
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use std::fmt;
+use std::ops::Deref;
+
+/// Equality and ordering follow Postgres' `network_cmp()`, rather than the text's, so sorting a
+/// `Vec<Cidr>` agrees with `ORDER BY` on a `cidr` column.  They're computed in Rust from the
+/// parsed address and netmask length, and so don't need a backend.  Text that isn't a valid
+/// address sorts after every valid one, by the text itself
+#[derive(Debug)]
+pub struct Cidr(pub String);
+
+impl PartialEq for Cidr {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Cidr {}
+
+impl PartialOrd for Cidr {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Cidr {
+    fn cmp(&self, other: &Self) -> Ordering {
+        super::inet::network_cmp(&self.0, &other.0)
+    }
+}
+
+impl Deref for Cidr {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+network_serde!(Cidr, "cidr");
+
+impl FromDatum for Cidr {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        _typoid: u32,
+    ) -> Option<Cidr> {
+        if is_null {
+            None
+        } else {
+            let cstr = direct_function_call::<&CStr>(pg_sys::cidr_out, vec![Some(datum)]);
+            Some(Cidr(
+                cstr.unwrap().to_str().expect("unable to convert &cstr cidr into &str").to_owned(),
+            ))
+        }
+    }
+}
+
+impl Cidr {
+    /// Convert to an [`Inet`].  Same as Postgres' `cidr::inet` cast
+    pub fn to_inet(&self) -> Inet {
+        // `cidr` and `inet` share the same on-disk representation, so this is a binary coercion
+        unsafe {
+            let datum = self.as_datum().expect("cidr_in returned NULL");
+            let inet =
+                Inet::from_datum(datum, false).expect("cidr to inet conversion returned NULL");
+
+            // and don't leak the 'cidr' datum Postgres created
+            pg_sys::pfree(datum.cast_mut_ptr());
+            inet
+        }
+    }
+
+    /// The abbreviated text form of this network.  Same as Postgres' `abbrev(cidr)`
+    pub fn abbrev(&self) -> String {
+        unsafe {
+            direct_function_call::<String>(pg_sys::cidr_abbrev, vec![self.as_datum()])
+                .expect("abbrev(cidr) returned NULL")
+        }
+    }
+
+    /// Do these two networks overlap, that is, does either contain or equal the other?  Same as
+    /// Postgres' `cidr && cidr`
+    pub fn overlaps(&self, other: &Cidr) -> bool {
+        unsafe {
+            direct_function_call::<bool>(
+                pg_sys::network_overlap,
+                vec![self.as_datum(), other.as_datum()],
+            )
+            .expect("cidr && cidr returned NULL")
+        }
+    }
+
+    /// The netmask length, in bits.  Same as Postgres' `masklen(cidr)`
+    pub fn masklen(&self) -> i32 {
+        unsafe {
+            direct_function_call::<i32>(pg_sys::network_masklen, vec![self.as_datum()])
+                .expect("masklen(cidr) returned NULL")
+        }
+    }
+
+    /// Collapse `networks` into the smallest set of networks covering exactly the same
+    /// addresses, in `ORDER BY` order.  Networks contained by another are dropped, and pairs of
+    /// adjacent networks that together make up a network one bit shorter are joined, repeatedly,
+    /// so `10.0.0.0/25` and `10.0.0.128/25` become `10.0.0.0/24`.
+    ///
+    /// IPv4 and IPv6 networks are never merged with each other.  To collapse a set of [`Inet`]s,
+    /// convert them with [`Inet::to_cidr()`] first
+    pub fn merge(networks: &[Cidr]) -> Vec<Cidr> {
+        let mut sorted = networks.iter().map(|cidr| Cidr(cidr.0.clone())).collect::<Vec<_>>();
+        sorted.sort();
+
+        // sorting puts a network before any it contains, so each one only needs checking
+        // against the last one kept
+        let mut merged: Vec<Cidr> = Vec::with_capacity(sorted.len());
+        for cidr in sorted {
+            if merged.last().map_or(false, |last| last.overlaps(&cidr)) {
+                continue;
+            }
+            merged.push(cidr);
+
+            while merged.len() >= 2 {
+                let b = &merged[merged.len() - 1];
+                let a = &merged[merged.len() - 2];
+                let masklen = a.masklen();
+                if masklen == 0 || masklen != b.masklen() {
+                    break;
+                }
+
+                let supernet = a.supernet();
+                if supernet != b.supernet() {
+                    break;
+                }
+                merged.truncate(merged.len() - 2);
+                merged.push(supernet);
+            }
+        }
+        merged
+    }
+
+    /// The network with a netmask one bit shorter that contains this one.  Same as Postgres'
+    /// `set_masklen(cidr, masklen(cidr) - 1)`
+    fn supernet(&self) -> Cidr {
+        unsafe {
+            direct_function_call::<Cidr>(
+                pg_sys::cidr_set_masklen,
+                vec![self.as_datum(), (self.masklen() - 1).into_datum()],
+            )
+            .expect("set_masklen(cidr) returned NULL")
+        }
+    }
+
+    /// Convert this value into a Postgres `cidr` Datum, allocated in the `CurrentMemoryContext`,
+    /// without consuming it
+    pub(crate) fn as_datum(&self) -> Option<pg_sys::Datum> {
+        let cstr =
+            std::ffi::CString::new(self.0.as_str()).expect("failed to convert cidr into CString");
+        unsafe {
+            direct_function_call_as_datum(pg_sys::cidr_in, vec![cstr.as_c_str().into_datum()])
+        }
+    }
+}
+
+impl IntoDatum for Cidr {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.as_datum()
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::CIDROID
+    }
+}
+
+impl From<String> for Cidr {
+    fn from(val: String) -> Self {
+        Cidr(val)
+    }
+}
+
+/// Generates IPv4 and IPv6 networks which Postgres always accepts as a `cidr`
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Cidr {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        super::inet::arbitrary_network(u, true).map(Cidr)
+    }
+}
+
+unsafe impl SqlTranslatable for Cidr {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("cidr"))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("cidr")))
+    }
+}
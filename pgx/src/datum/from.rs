@@ -157,7 +157,7 @@ impl FromDatum for bool {
     }
 }
 
-/// for `"char"`
+/// for `"char"`, although [`PgChar`][crate::PgChar] is clearer about its meaning
 impl FromDatum for i8 {
     #[inline]
     unsafe fn from_polymorphic_datum(
@@ -325,6 +325,10 @@ impl FromDatum for String {
     }
 }
 
+/// A one-character `text` or `varchar`, as a Unicode scalar value.  This is unrelated to
+/// Postgres' single-byte `"char"` type, which is [`PgChar`](crate::PgChar).
+///
+/// Raises an ERROR if the text isn't exactly one character long
 impl FromDatum for char {
     #[inline]
     unsafe fn from_polymorphic_datum(
@@ -332,8 +336,13 @@ impl FromDatum for char {
         is_null: bool,
         typoid: pg_sys::Oid,
     ) -> Option<char> {
-        FromDatum::from_polymorphic_datum(datum, is_null, typoid)
-            .and_then(|s: &str| s.chars().next())
+        FromDatum::from_polymorphic_datum(datum, is_null, typoid).map(|s: &str| {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => c,
+                _ => panic!("expected exactly one character, but got {:?}", s),
+            }
+        })
     }
 }
 
@@ -8,119 +8,620 @@ Use of this source code is governed by the MIT license that can be found in the
 */
 
 use crate::{
-    direct_function_call, direct_function_call_as_datum, pg_sys, pg_try, FromDatum, IntoDatum,
+    direct_function_call, direct_function_call_as_datum, ereport, pg_sys, pg_try, Cidr, FromDatum,
+    IntoDatum, PgLogLevel, PgOid, PgSqlErrorCode, Spi, StringInfo,
 };
 use pgx_utils::sql_entity_graph::metadata::{
     ArgumentError, Returns, ReturnsError, SqlMapping, SqlTranslatable,
 };
 use serde::de::{Error, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::cmp::Ordering;
 use std::ffi::CStr;
 use std::fmt;
-use std::ops::Deref;
+use std::net::IpAddr;
+use std::ops::{Add, BitAnd, BitOr, Deref, Not, Sub};
 
-#[derive(Debug, Ord, PartialOrd, Eq, PartialEq)]
+/// Equality and ordering follow Postgres' `network_cmp()`, rather than the text's, so sorting a
+/// `Vec<Inet>` agrees with `ORDER BY` on an `inet` column.  They're computed in Rust from the
+/// parsed address and netmask length, and so don't need a backend.  Text that isn't a valid
+/// address sorts after every valid one, by the text itself
+#[derive(Debug)]
 pub struct Inet(pub String);
 
-impl Deref for Inet {
-    type Target = str;
+impl PartialEq for Inet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+impl Eq for Inet {}
+
+impl PartialOrd for Inet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-impl Serialize for Inet {
-    fn serialize<S>(&self, serializer: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&self.0)
+impl Ord for Inet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        network_cmp(&self.0, &other.0)
     }
 }
 
-impl<'de> Deserialize<'de> for Inet {
-    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        struct InetVisitor;
-        impl<'de> Visitor<'de> for InetVisitor {
-            type Value = Inet;
+impl Deref for Inet {
+    type Target = str;
 
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("a quoted JSON string in proper inet form")
-            }
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+// `Inet` and `Cidr` are both (de)serialized as their text form, which is only accepted if Postgres
+// can parse it as the type
+macro_rules! network_serde {
+    ($type:ident, $name:literal) => {
+        impl Serialize for $type {
+            fn serialize<S>(
+                &self,
+                serializer: S,
+            ) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
             where
-                E: Error,
+                S: Serializer,
             {
-                self.visit_string(v.to_owned())
+                serializer.serialize_str(&self.0)
             }
+        }
 
-            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
             where
-                E: Error,
+                D: Deserializer<'de>,
             {
-                // try to convert the provided String value into a Postgres Numeric Datum
-                // if it doesn't raise an ERROR, then we're good
-                unsafe {
-                    pg_try(|| {
-                        // this might throw, but that's okay
-                        let datum = Inet(v.clone()).into_datum().unwrap();
-
-                        // and don't leak the 'inet' datum Postgres created
-                        pg_sys::pfree(datum.cast_mut_ptr());
-
-                        // we have it as a valid String
-                        Ok(Inet(v.clone()))
-                    })
-                    .unwrap_or_else(|| Err(Error::custom(format!("invalid inet value: {}", v))))
+                struct NetworkVisitor;
+                impl<'de> Visitor<'de> for NetworkVisitor {
+                    type Value = $type;
+
+                    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                        formatter.write_str(concat!(
+                            "a quoted JSON string in proper ",
+                            $name,
+                            " form"
+                        ))
+                    }
+
+                    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        self.visit_string(v.to_owned())
+                    }
+
+                    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+                    where
+                        E: Error,
+                    {
+                        // try to convert the provided String value into a Postgres Datum of the
+                        // type.  if it doesn't raise an ERROR, then we're good
+                        unsafe {
+                            pg_try(|| {
+                                // this might throw, but that's okay
+                                let datum = $type(v.clone()).into_datum().unwrap();
+
+                                // and don't leak the datum Postgres created
+                                pg_sys::pfree(datum.cast_mut_ptr());
+
+                                // we have it as a valid String
+                                Ok($type(v.clone()))
+                            })
+                            .unwrap_or_else_sqlerrcode(
+                                &[PgSqlErrorCode::ERRCODE_INVALID_TEXT_REPRESENTATION],
+                                || {
+                                    Err(Error::custom(format!(
+                                        concat!("invalid ", $name, " value: {}"),
+                                        v
+                                    )))
+                                },
+                            )
+                        }
+                    }
                 }
+
+                deserializer.deserialize_str(NetworkVisitor)
             }
         }
+    };
+}
+pub(super) use network_serde;
+
+network_serde!(Inet, "inet");
 
-        deserializer.deserialize_str(InetVisitor)
+impl FromDatum for Inet {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        typoid: u32,
+    ) -> Option<Inet> {
+        InetRef::from_polymorphic_datum(datum, is_null, typoid).map(Inet::from)
     }
 }
 
-impl FromDatum for Inet {
+/// A borrowed Postgres `inet`, in the text form `inet_out` renders into the `CurrentMemoryContext`.
+///
+/// Converting one from a Datum doesn't copy that text into a Rust `String`, as [`Inet`] does,
+/// making it the cheaper choice when scanning many rows.  It's only valid until that memory
+/// context is reset, however, so convert it into an [`Inet`] to keep it any longer
+#[derive(Clone, Copy, Debug)]
+pub struct InetRef<'a>(&'a str);
+
+impl<'a> InetRef<'a> {
+    /// The address as text, such as `192.168.0.1/24`
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl<'a> Deref for InetRef<'a> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a> fmt::Display for InetRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl<'a> From<InetRef<'a>> for Inet {
+    fn from(inet: InetRef<'a>) -> Self {
+        Inet(inet.0.to_owned())
+    }
+}
+
+impl<'a> IntoDatum for InetRef<'a> {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        inet_in(self.0)
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::INETOID
+    }
+}
+
+impl<'a> FromDatum for InetRef<'a> {
     unsafe fn from_polymorphic_datum(
         datum: pg_sys::Datum,
         is_null: bool,
         _typoid: u32,
-    ) -> Option<Inet> {
+    ) -> Option<InetRef<'a>> {
         if is_null {
             None
         } else {
             let cstr = direct_function_call::<&CStr>(pg_sys::inet_out, vec![Some(datum)]);
-            Some(Inet(
-                cstr.unwrap().to_str().expect("unable to convert &cstr inet into &str").to_owned(),
-            ))
+            Some(InetRef(cstr.unwrap().to_str().expect("unable to convert &cstr inet into &str")))
         }
     }
 }
 
-impl IntoDatum for Inet {
-    fn into_datum(self) -> Option<pg_sys::Datum> {
-        let cstr = std::ffi::CString::new(self.0).expect("failed to convert inet into CString");
+unsafe impl<'a> SqlTranslatable for InetRef<'a> {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("inet"))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("inet")))
+    }
+}
+
+impl Inet {
+    /// Extract the IP address as text, without the netmask.  Same as Postgres' `host(inet)`
+    pub fn host(&self) -> String {
+        unsafe {
+            direct_function_call::<String>(pg_sys::network_host, vec![self.as_datum()])
+                .expect("host(inet) returned NULL")
+        }
+    }
+
+    /// The network part of the address, zeroing out whatever is to the right of the netmask.
+    /// Same as Postgres' `network(inet)`
+    pub fn network(&self) -> Inet {
+        self.call_returning_inet(pg_sys::network_network)
+    }
+
+    /// The broadcast address for the address's network.  Same as Postgres' `broadcast(inet)`
+    pub fn broadcast(&self) -> Inet {
+        self.call_returning_inet(pg_sys::network_broadcast)
+    }
+
+    /// The netmask for the address's network.  Same as Postgres' `netmask(inet)`
+    pub fn netmask(&self) -> Inet {
+        self.call_returning_inet(pg_sys::network_netmask)
+    }
+
+    /// The netmask length, in bits.  Same as Postgres' `masklen(inet)`
+    pub fn masklen(&self) -> i32 {
+        unsafe {
+            direct_function_call::<i32>(pg_sys::network_masklen, vec![self.as_datum()])
+                .expect("masklen(inet) returned NULL")
+        }
+    }
+
+    /// Convert to a [`Cidr`], zeroing out whatever is to the right of the netmask.  Same as
+    /// Postgres' `inet::cidr` cast
+    pub fn to_cidr(&self) -> Cidr {
         unsafe {
-            direct_function_call_as_datum(pg_sys::inet_in, vec![cstr.as_c_str().into_datum()])
+            direct_function_call::<Cidr>(pg_sys::inet_to_cidr, vec![self.as_datum()])
+                .expect("cidr(inet) returned NULL")
         }
     }
 
+    /// The abbreviated text form of this address.  Same as Postgres' `abbrev(inet)`
+    pub fn abbrev(&self) -> String {
+        unsafe {
+            direct_function_call::<String>(pg_sys::inet_abbrev, vec![self.as_datum()])
+                .expect("abbrev(inet) returned NULL")
+        }
+    }
+
+    /// Do these two networks overlap, that is, does either contain or equal the other?  Same as
+    /// Postgres' `inet && inet`
+    pub fn overlaps(&self, other: &Inet) -> bool {
+        unsafe {
+            direct_function_call::<bool>(
+                pg_sys::network_overlap,
+                vec![self.as_datum(), other.as_datum()],
+            )
+            .expect("inet && inet returned NULL")
+        }
+    }
+
+    /// This address in Postgres' binary wire format, as sent by `inet_send()` for binary `COPY`
+    /// and the extended query protocol
+    pub fn to_binary(&self) -> Vec<u8> {
+        unsafe {
+            direct_function_call::<Vec<u8>>(pg_sys::inet_send, vec![self.as_datum()])
+                .expect("inet_send returned NULL")
+        }
+    }
+
+    /// Parse an address in Postgres' binary wire format, such as made by [`Inet::to_binary()`],
+    /// by way of `inet_recv()`
+    pub fn from_binary(bytes: &[u8]) -> Result<Inet, InetBinaryError> {
+        let buf = StringInfo::from(bytes).into_pg();
+        let datum = unsafe {
+            pg_try(|| Ok(direct_function_call_as_datum(pg_sys::inet_recv, vec![Some(buf.into())])))
+                .unwrap_or_else_sqlerrcode(
+                    &[
+                        PgSqlErrorCode::ERRCODE_INVALID_BINARY_REPRESENTATION,
+                        // raised when the input is cut short
+                        PgSqlErrorCode::ERRCODE_PROTOCOL_VIOLATION,
+                    ],
+                    || Err(InetBinaryError::InvalidBinaryRepresentation),
+                )?
+                .expect("inet_recv returned NULL")
+        };
+
+        let remaining = unsafe { (*buf).len - (*buf).cursor } as usize;
+        if remaining > 0 {
+            return Err(InetBinaryError::TrailingBytes(remaining));
+        }
+        Ok(unsafe { Inet::from_datum(datum, false) }.expect("inet_recv returned NULL"))
+    }
+
+    /// Is this address equal to any address in `set`?  Same as Postgres' `inet = ANY(inet[])`,
+    /// so addresses only match if their netmasks do too.
+    ///
+    /// An empty `set` never matches.  This runs a query through SPI, and so must be called from
+    /// within a transaction
+    pub fn any_eq(&self, set: &[Inet]) -> bool {
+        self.compare_array("SELECT $1 = ANY($2)", set)
+    }
+
+    /// Does this network strictly contain every address in `set`?  Same as Postgres'
+    /// `inet >> ALL(inet[])`.
+    ///
+    /// An empty `set` always matches.  This runs a query through SPI, and so must be called from
+    /// within a transaction
+    pub fn all_contains(&self, set: &[Inet]) -> bool {
+        self.compare_array("SELECT $1 >> ALL($2)", set)
+    }
+
+    /// Let Postgres evaluate a scalar-array comparison of `self` (`$1`) with `set` (`$2`), so
+    /// that its semantics are exactly those of the `inet` operators
+    fn compare_array(&self, query: &str, set: &[Inet]) -> bool {
+        let array = set.iter().collect::<Vec<&Inet>>();
+        Spi::get_one_with_args::<bool>(
+            query,
+            vec![
+                (PgOid::from(Inet::type_oid()), self.as_datum()),
+                (PgOid::from(Vec::<&Inet>::type_oid()), array.into_datum()),
+            ],
+        )
+        .expect("inet array comparison returned NULL")
+    }
+
+    fn call_with_inet(
+        &self,
+        func: unsafe fn(pg_sys::FunctionCallInfo) -> pg_sys::Datum,
+        other: Option<pg_sys::Datum>,
+    ) -> Inet {
+        unsafe {
+            direct_function_call::<Inet>(func, vec![self.as_datum(), other])
+                .expect("inet operator returned NULL")
+        }
+    }
+
+    fn call_returning_inet(
+        &self,
+        func: unsafe fn(pg_sys::FunctionCallInfo) -> pg_sys::Datum,
+    ) -> Inet {
+        unsafe {
+            direct_function_call::<Inet>(func, vec![self.as_datum()])
+                .expect("inet function returned NULL")
+        }
+    }
+
+    /// Convert this value into a Postgres `inet` Datum, allocated in the `CurrentMemoryContext`,
+    /// without consuming it
+    pub(crate) fn as_datum(&self) -> Option<pg_sys::Datum> {
+        inet_in(&self.0)
+    }
+}
+
+/// Parse `text` into a Postgres `inet` Datum, allocated in the `CurrentMemoryContext`
+fn inet_in(text: &str) -> Option<pg_sys::Datum> {
+    let cstr = std::ffi::CString::new(text).expect("failed to convert inet into CString");
+    unsafe { direct_function_call_as_datum(pg_sys::inet_in, vec![cstr.as_c_str().into_datum()]) }
+}
+
+/// An `inet` or `cidr` value, parsed from its text for comparison
+struct Network {
+    is_ipv6: bool,
+    addr: u128,
+    bits: u32,
+}
+
+impl Network {
+    fn parse(text: &str) -> Option<Network> {
+        let (addr, bits) = match text.split_once('/') {
+            Some((addr, bits)) => (addr, Some(bits.parse::<u32>().ok()?)),
+            None => (text, None),
+        };
+
+        let network = if addr.contains(':') {
+            let addr = addr.parse::<std::net::Ipv6Addr>().ok()?;
+            Network { is_ipv6: true, addr: u128::from(addr), bits: bits.unwrap_or(128) }
+        } else {
+            // a `cidr` may abbreviate trailing zero octets, as in `10.1/16`
+            let mut octets = [0u8; 4];
+            let parts = addr.split('.').collect::<Vec<_>>();
+            if parts.len() > 4 {
+                return None;
+            }
+            for (octet, part) in octets.iter_mut().zip(parts) {
+                *octet = part.parse().ok()?;
+            }
+            Network {
+                is_ipv6: false,
+                addr: u32::from_be_bytes(octets) as u128,
+                bits: bits.unwrap_or(32),
+            }
+        };
+
+        if network.bits > network.max_bits() {
+            return None;
+        }
+        Some(network)
+    }
+
+    fn max_bits(&self) -> u32 {
+        if self.is_ipv6 {
+            128
+        } else {
+            32
+        }
+    }
+
+    /// The leading `bits` bits of the address
+    fn prefix(&self, bits: u32) -> u128 {
+        match bits {
+            0 => 0,
+            bits => self.addr >> (self.max_bits() - bits),
+        }
+    }
+
+    /// Same as Postgres' `network_cmp()`: IPv4 before IPv6, then by the network part common to
+    /// both netmasks, then by netmask length, then by the whole address
+    fn cmp(&self, other: &Network) -> Ordering {
+        self.is_ipv6.cmp(&other.is_ipv6).then_with(|| {
+            let common = self.bits.min(other.bits);
+            self.prefix(common)
+                .cmp(&other.prefix(common))
+                .then(self.bits.cmp(&other.bits))
+                .then(self.addr.cmp(&other.addr))
+        })
+    }
+}
+
+/// Compare the text of two `inet` or `cidr` values as Postgres' `network_cmp()` does
+pub(crate) fn network_cmp(left: &str, right: &str) -> Ordering {
+    match (Network::parse(left), Network::parse(right)) {
+        (Some(left), Some(right)) => left.cmp(&right),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => left.cmp(right),
+    }
+}
+
+/// Why [`Inet::from_binary()`] couldn't parse its input
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InetBinaryError {
+    #[error("not a valid binary inet value")]
+    InvalidBinaryRepresentation,
+    #[error("{0} unexpected byte(s) after the binary inet value")]
+    TrailingBytes(usize),
+}
+
+/// Bitwise NOT of the address.  Same as Postgres' `~ inet`
+impl Not for Inet {
+    type Output = Inet;
+
+    fn not(self) -> Inet {
+        self.call_returning_inet(pg_sys::inetnot)
+    }
+}
+
+/// Bitwise AND of two addresses.  Same as Postgres' `inet & inet`, which raises an ERROR if
+/// they're of different families
+impl BitAnd for Inet {
+    type Output = Inet;
+
+    fn bitand(self, rhs: Inet) -> Inet {
+        self.call_with_inet(pg_sys::inetand, rhs.as_datum())
+    }
+}
+
+/// Bitwise OR of two addresses.  Same as Postgres' `inet | inet`, which raises an ERROR if
+/// they're of different families
+impl BitOr for Inet {
+    type Output = Inet;
+
+    fn bitor(self, rhs: Inet) -> Inet {
+        self.call_with_inet(pg_sys::inetor, rhs.as_datum())
+    }
+}
+
+/// Add an offset to the address.  Same as Postgres' `inet + bigint`, which raises an ERROR if
+/// the result is out of range
+impl Add<i64> for Inet {
+    type Output = Inet;
+
+    fn add(self, rhs: i64) -> Inet {
+        self.call_with_inet(pg_sys::inetpl, rhs.into_datum())
+    }
+}
+
+/// Subtract an offset from the address.  Same as Postgres' `inet - bigint`, which raises an
+/// ERROR if the result is out of range
+impl Sub<i64> for Inet {
+    type Output = Inet;
+
+    fn sub(self, rhs: i64) -> Inet {
+        self.call_with_inet(pg_sys::inetmi_int8, rhs.into_datum())
+    }
+}
+
+/// The numeric distance between two addresses.  Same as Postgres' `inet - inet`, which raises
+/// an ERROR if they're of different families
+impl Sub for Inet {
+    type Output = i64;
+
+    fn sub(self, rhs: Inet) -> i64 {
+        unsafe {
+            direct_function_call::<i64>(pg_sys::inetmi, vec![self.as_datum(), rhs.as_datum()])
+                .expect("inet - inet returned NULL")
+        }
+    }
+}
+
+impl IntoDatum for Inet {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.as_datum()
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::INETOID
+    }
+}
+
+/// Converts without consuming, or cloning, the `Inet`
+impl IntoDatum for &Inet {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.as_datum()
+    }
+
     fn type_oid() -> u32 {
         pg_sys::INETOID
     }
 }
 
+/// for `inet`, as a single host address
+impl IntoDatum for IpAddr {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        inet_in(&self.to_string())
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::INETOID
+    }
+}
+
+/// for `inet`.  Raises a Postgres ERROR if the value has a netmask shorter than its address, such
+/// as `192.168.0.0/24`, as that's a network rather than a single host address
+impl FromDatum for IpAddr {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        typoid: u32,
+    ) -> Option<IpAddr> {
+        let inet = InetRef::from_polymorphic_datum(datum, is_null, typoid)?;
+        if inet.contains('/') {
+            ereport(
+                PgLogLevel::ERROR,
+                PgSqlErrorCode::ERRCODE_DATA_EXCEPTION,
+                &format!("inet `{}` has a netmask, so can't be represented as an IpAddr", inet),
+                file!(),
+                line!(),
+                column!(),
+            );
+            unreachable!("ereport(ERROR) returned")
+        }
+        Some(inet.parse().expect("inet_out returned an invalid address"))
+    }
+}
+
 impl From<String> for Inet {
     fn from(val: String) -> Self {
         Inet(val)
     }
 }
 
+/// Generates IPv4 and IPv6 addresses, with netmasks, which Postgres always accepts as an `inet`
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Inet {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        arbitrary_network(u, false).map(Inet)
+    }
+}
+
+/// An arbitrary IPv4 or IPv6 address and netmask length, in Postgres' text form.  If
+/// `zero_host_bits`, whatever is to the right of the netmask is zeroed, as a `cidr` requires
+#[cfg(feature = "arbitrary")]
+pub(crate) fn arbitrary_network(
+    u: &mut arbitrary::Unstructured<'_>,
+    zero_host_bits: bool,
+) -> arbitrary::Result<String> {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    if u.arbitrary::<bool>()? {
+        let bits = u.int_in_range(0..=32)?;
+        let mut addr = u32::from_be_bytes(u.arbitrary()?);
+        if zero_host_bits {
+            addr &= u32::MAX.checked_shl(32 - bits).unwrap_or(0);
+        }
+        Ok(format!("{}/{}", Ipv4Addr::from(addr), bits))
+    } else {
+        let bits = u.int_in_range(0..=128)?;
+        let mut addr = u128::from_be_bytes(u.arbitrary()?);
+        if zero_host_bits {
+            addr &= u128::MAX.checked_shl(128 - bits).unwrap_or(0);
+        }
+        Ok(format!("{}/{}", Ipv6Addr::from(addr), bits))
+    }
+}
+
 unsafe impl SqlTranslatable for Inet {
     fn argument_sql() -> Result<SqlMapping, ArgumentError> {
         Ok(SqlMapping::literal("inet"))
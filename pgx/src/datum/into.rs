@@ -135,7 +135,7 @@ impl IntoDatum for bool {
     }
 }
 
-/// for "char"
+/// for "char", although [`PgChar`][crate::PgChar] is clearer about its meaning
 impl IntoDatum for i8 {
     fn into_datum(self) -> Option<pg_sys::Datum> {
         Some(pg_sys::Datum::from(self))
@@ -233,6 +233,9 @@ impl IntoDatum for PgOid {
 }
 
 /// for text, varchar
+///
+/// The string is copied straight into a `text` allocated in the `CurrentMemoryContext`, so
+/// there's no need for an owned `String` first
 impl<'a> IntoDatum for &'a str {
     #[inline]
     fn into_datum(self) -> Option<pg_sys::Datum> {
@@ -286,10 +289,14 @@ impl IntoDatum for &String {
     }
 }
 
+/// A one-character `varchar`.  This is unrelated to Postgres' single-byte `"char"` type, which is
+/// [`PgChar`](crate::PgChar)
 impl IntoDatum for char {
     #[inline]
     fn into_datum(self) -> Option<pg_sys::Datum> {
-        self.to_string().into_datum()
+        let mut buf = [0; 4];
+        let s: &str = self.encode_utf8(&mut buf);
+        s.into_datum()
     }
 
     fn type_oid() -> u32 {
@@ -298,7 +305,7 @@ impl IntoDatum for char {
 
     #[inline]
     fn is_compatible_with(other: Oid) -> bool {
-        Self::type_oid() == other || other == pg_sys::VARCHAROID
+        other == pg_sys::VARCHAROID || other == pg_sys::TEXTOID
     }
 }
 
@@ -330,6 +337,9 @@ impl<'a> IntoDatum for &'a crate::cstr_core::CStr {
 }
 
 /// for bytea
+///
+/// The bytes are copied straight into a `bytea` allocated in the `CurrentMemoryContext`, so
+/// there's no need for an owned `Vec<u8>` first
 impl<'a> IntoDatum for &'a [u8] {
     #[inline]
     fn into_datum(self) -> Option<pg_sys::Datum> {
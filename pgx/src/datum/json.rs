@@ -128,16 +128,200 @@ impl IntoDatum for Json {
     }
 }
 
-/// for jsonb
-impl IntoDatum for JsonB {
-    fn into_datum(self) -> Option<pg_sys::Datum> {
-        let string = serde_json::to_string(&self.0).expect("failed to serialize JsonB value");
-        let cstring =
-            std::ffi::CString::new(string).expect("string version of jsonb is not valid UTF8");
+impl JsonB {
+    /// Does this `jsonb` value contain `other` at its top level?  This is the same as
+    /// Postgres' `@>` operator.
+    pub fn contains(&self, other: &JsonB) -> bool {
+        unsafe {
+            direct_function_call::<bool>(
+                pg_sys::jsonb_contains,
+                vec![self.as_datum(), other.as_datum()],
+            )
+            .expect("jsonb_contains returned NULL")
+        }
+    }
 
+    /// Is this `jsonb` value contained within `other`?  This is the same as Postgres' `<@`
+    /// operator.
+    pub fn contained_by(&self, other: &JsonB) -> bool {
         unsafe {
-            direct_function_call_as_datum(pg_sys::jsonb_in, vec![Some(cstring.as_ptr().into())])
+            direct_function_call::<bool>(
+                pg_sys::jsonb_contained,
+                vec![self.as_datum(), other.as_datum()],
+            )
+            .expect("jsonb_contained returned NULL")
+        }
+    }
+
+    /// Does the string `key` exist as a top-level key or array element within this `jsonb` value?
+    /// This is the same as Postgres' `?` operator.
+    pub fn has_key(&self, key: &str) -> bool {
+        unsafe {
+            direct_function_call::<bool>(
+                pg_sys::jsonb_exists,
+                vec![self.as_datum(), key.into_datum()],
+            )
+            .expect("jsonb_exists returned NULL")
+        }
+    }
+
+    /// Do any of the strings in `keys` exist as top-level keys or array elements within this
+    /// `jsonb` value?  This is the same as Postgres' `?|` operator.
+    pub fn has_any_key(&self, keys: &[&str]) -> bool {
+        unsafe {
+            direct_function_call::<bool>(
+                pg_sys::jsonb_exists_any,
+                vec![self.as_datum(), keys.into_datum()],
+            )
+            .expect("jsonb_exists_any returned NULL")
+        }
+    }
+
+    /// Do all of the strings in `keys` exist as top-level keys or array elements within this
+    /// `jsonb` value?  This is the same as Postgres' `?&` operator.
+    pub fn has_all_keys(&self, keys: &[&str]) -> bool {
+        unsafe {
+            direct_function_call::<bool>(
+                pg_sys::jsonb_exists_all,
+                vec![self.as_datum(), keys.into_datum()],
+            )
+            .expect("jsonb_exists_all returned NULL")
+        }
+    }
+
+    /// Convert this value into a Postgres `jsonb` Datum, allocated in the `CurrentMemoryContext`,
+    /// without consuming it
+    ///
+    /// The `jsonb` is built directly from the `serde_json::Value`, rather than printing it as JSON
+    /// text for `jsonb_in` to parse again, which matters for large documents
+    fn as_datum(&self) -> Option<pg_sys::Datum> {
+        unsafe {
+            let jsonb = match &self.0 {
+                Value::Array(_) | Value::Object(_) => {
+                    let mut state = std::ptr::null_mut();
+                    pg_sys::JsonbValueToJsonb(push_jsonb_container(&mut state, &self.0))
+                }
+                // `JsonbValueToJsonb()` wraps a bare scalar in the "raw scalar" array jsonb wants
+                scalar => pg_sys::JsonbValueToJsonb(&mut jsonb_scalar(scalar)),
+            };
+            Some(jsonb.into())
+        }
+    }
+}
+
+/// Push an array or object, and everything in it, onto a `jsonb` parse state, returning the
+/// container's `JsonbValue` once it's complete.
+///
+/// String values are borrowed from `value`, and are only copied into the `jsonb` by
+/// `JsonbValueToJsonb()`, so `value` must outlive that call
+unsafe fn push_jsonb_container(
+    state: &mut *mut pg_sys::JsonbParseState,
+    value: &Value,
+) -> *mut pg_sys::JsonbValue {
+    match value {
+        Value::Array(elems) => {
+            pg_sys::pushJsonbValue(
+                state,
+                pg_sys::JsonbIteratorToken_WJB_BEGIN_ARRAY,
+                std::ptr::null_mut(),
+            );
+            for elem in elems {
+                push_jsonb_value(state, pg_sys::JsonbIteratorToken_WJB_ELEM, elem);
+            }
+            pg_sys::pushJsonbValue(
+                state,
+                pg_sys::JsonbIteratorToken_WJB_END_ARRAY,
+                std::ptr::null_mut(),
+            )
+        }
+        Value::Object(map) => {
+            pg_sys::pushJsonbValue(
+                state,
+                pg_sys::JsonbIteratorToken_WJB_BEGIN_OBJECT,
+                std::ptr::null_mut(),
+            );
+            // Postgres sorts the keys, and drops duplicates, once the object is complete
+            for (key, value) in map {
+                pg_sys::pushJsonbValue(
+                    state,
+                    pg_sys::JsonbIteratorToken_WJB_KEY,
+                    &mut jsonb_string(key),
+                );
+                push_jsonb_value(state, pg_sys::JsonbIteratorToken_WJB_VALUE, value);
+            }
+            pg_sys::pushJsonbValue(
+                state,
+                pg_sys::JsonbIteratorToken_WJB_END_OBJECT,
+                std::ptr::null_mut(),
+            )
         }
+        _ => unreachable!("not a jsonb container"),
+    }
+}
+
+unsafe fn push_jsonb_value(
+    state: &mut *mut pg_sys::JsonbParseState,
+    token: pg_sys::JsonbIteratorToken,
+    value: &Value,
+) {
+    match value {
+        Value::Array(_) | Value::Object(_) => {
+            push_jsonb_container(state, value);
+        }
+        scalar => {
+            pg_sys::pushJsonbValue(state, token, &mut jsonb_scalar(scalar));
+        }
+    }
+}
+
+unsafe fn jsonb_scalar(value: &Value) -> pg_sys::JsonbValue {
+    let mut jbv = pg_sys::JsonbValue::default();
+    match value {
+        Value::Null => jbv.type_ = pg_sys::jbvType_jbvNull,
+        Value::Bool(b) => {
+            jbv.type_ = pg_sys::jbvType_jbvBool;
+            jbv.val.boolean = *b;
+        }
+        Value::Number(n) => {
+            let cstring = std::ffi::CString::new(n.to_string()).unwrap();
+            let numeric = direct_function_call_as_datum(
+                pg_sys::numeric_in,
+                vec![
+                    cstring.as_c_str().into_datum(),
+                    pg_sys::InvalidOid.into_datum(),
+                    (-1i32).into_datum(),
+                ],
+            )
+            .expect("numeric_in returned NULL");
+            jbv.type_ = pg_sys::jbvType_jbvNumeric;
+            jbv.val.numeric = numeric.cast_mut_ptr();
+        }
+        Value::String(s) => jbv = jsonb_string(s),
+        Value::Array(_) | Value::Object(_) => unreachable!("not a jsonb scalar"),
+    }
+    jbv
+}
+
+/// A `jbvString` which borrows `s`
+fn jsonb_string(s: &str) -> pg_sys::JsonbValue {
+    // the same limits `jsonb_in` enforces
+    if s.len() > pg_sys::JENTRY_OFFLENMASK as usize {
+        panic!("string too long to represent as jsonb string")
+    } else if s.contains('\0') {
+        panic!("unsupported Unicode escape sequence: \\u0000 cannot be converted to text")
+    }
+
+    let mut jbv = pg_sys::JsonbValue::default();
+    jbv.type_ = pg_sys::jbvType_jbvString;
+    jbv.val.string.len = s.len() as i32;
+    jbv.val.string.val = s.as_ptr() as *mut std::os::raw::c_char;
+    jbv
+}
+
+/// for jsonb
+impl IntoDatum for JsonB {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.as_datum()
     }
 
     fn type_oid() -> u32 {
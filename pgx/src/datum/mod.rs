@@ -12,6 +12,8 @@ Use of this source code is governed by the MIT license that can be found in the
 mod anyarray;
 mod anyelement;
 mod array;
+mod bytea;
+mod cidr;
 mod date;
 mod from;
 mod geo;
@@ -21,6 +23,8 @@ mod into;
 mod item_pointer_data;
 mod json;
 mod numeric;
+mod pg_char;
+mod std_time;
 mod time;
 mod time_stamp;
 mod time_stamp_with_timezone;
@@ -34,6 +38,8 @@ pub use self::uuid::*;
 pub use anyarray::*;
 pub use anyelement::*;
 pub use array::*;
+pub use bytea::*;
+pub use cidr::*;
 pub use date::*;
 pub use from::*;
 pub use geo::*;
@@ -43,6 +49,7 @@ pub use into::*;
 pub use item_pointer_data::*;
 pub use json::*;
 pub use numeric::*;
+pub use pg_char::*;
 use once_cell::sync::Lazy;
 use std::any::TypeId;
 pub use time_stamp::*;
@@ -15,8 +15,14 @@ use pgx_utils::sql_entity_graph::metadata::{
 use serde::de::{Error, Visitor};
 use serde::{de, Deserialize, Deserializer, Serialize};
 use serde_json::Number;
+use std::cmp::Ordering;
 use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
+/// A Postgres `numeric`, as text.
+///
+/// Comparisons (`==`, `<`, sorting, etc) follow Postgres' `numeric_cmp()`, but are computed in
+/// Rust from the text, and so don't need a backend
 #[derive(Serialize, Debug)]
 pub struct Numeric(pub String);
 
@@ -158,6 +164,25 @@ impl From<f64> for Numeric {
     }
 }
 
+/// Losslessly convert from a [`rust_decimal::Decimal`]
+#[cfg(feature = "rust-decimal")]
+impl From<rust_decimal::Decimal> for Numeric {
+    fn from(val: rust_decimal::Decimal) -> Self {
+        Numeric(val.to_string())
+    }
+}
+
+/// Convert to a [`rust_decimal::Decimal`], failing rather than rounding if the value has more
+/// precision than a `Decimal` can represent, or is `NaN` or infinite
+#[cfg(feature = "rust-decimal")]
+impl TryFrom<Numeric> for rust_decimal::Decimal {
+    type Error = rust_decimal::Error;
+
+    fn try_from(val: Numeric) -> Result<Self, Self::Error> {
+        rust_decimal::Decimal::from_str_exact(&val.0)
+    }
+}
+
 impl FromDatum for Numeric {
     unsafe fn from_polymorphic_datum(
         datum: pg_sys::Datum,
@@ -178,10 +203,12 @@ impl FromDatum for Numeric {
     }
 }
 
-impl IntoDatum for Numeric {
-    fn into_datum(self) -> Option<pg_sys::Datum> {
-        let cstring =
-            std::ffi::CString::new(self.0).expect("failed to convert numeric string into CString");
+impl Numeric {
+    /// Convert this value into a Postgres `numeric` Datum, allocated in the `CurrentMemoryContext`,
+    /// without consuming it
+    pub(crate) fn as_datum(&self) -> Option<pg_sys::Datum> {
+        let cstring = std::ffi::CString::new(self.0.as_str())
+            .expect("failed to convert numeric string into CString");
         let cstr = cstring.as_c_str();
 
         unsafe {
@@ -192,6 +219,177 @@ impl IntoDatum for Numeric {
         }
     }
 
+    fn call_returning_numeric(
+        func: unsafe fn(pg_sys::FunctionCallInfo) -> pg_sys::Datum,
+        args: Vec<Option<pg_sys::Datum>>,
+    ) -> Numeric {
+        unsafe {
+            direct_function_call::<Numeric>(func, args).expect("numeric function returned NULL")
+        }
+    }
+}
+
+// Arithmetic is Postgres', by way of `numeric_add()` and friends, so the result's scale, and any
+// rounding, is exactly what the same SQL expression would produce.  Like SQL, dividing by zero
+// raises an ERROR
+macro_rules! numeric_binary_op {
+    ($trait:ident, $method:ident, $func:ident) => {
+        impl $trait for Numeric {
+            type Output = Numeric;
+
+            fn $method(self, rhs: Numeric) -> Numeric {
+                Numeric::call_returning_numeric(
+                    pg_sys::$func,
+                    vec![self.as_datum(), rhs.as_datum()],
+                )
+            }
+        }
+    };
+}
+
+numeric_binary_op!(Add, add, numeric_add);
+numeric_binary_op!(Sub, sub, numeric_sub);
+numeric_binary_op!(Mul, mul, numeric_mul);
+numeric_binary_op!(Div, div, numeric_div);
+numeric_binary_op!(Rem, rem, numeric_mod);
+
+impl Neg for Numeric {
+    type Output = Numeric;
+
+    fn neg(self) -> Numeric {
+        Numeric::call_returning_numeric(pg_sys::numeric_uminus, vec![self.as_datum()])
+    }
+}
+
+/// Equality and ordering follow Postgres' `numeric_cmp()`, so `1.0 == 1.00` and, unlike `f64`,
+/// `NaN` equals itself and sorts after every other value.  Text that isn't a valid `numeric`
+/// sorts after `NaN`, by the text itself
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Numeric {}
+
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Numeric {
+    fn cmp(&self, other: &Self) -> Ordering {
+        numeric_cmp(&self.0, &other.0)
+    }
+}
+
+/// A `numeric` value, parsed from its text for comparison.  The variants are in Postgres' order
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum NumericValue {
+    NegativeInfinity,
+    Finite(FiniteNumeric),
+    Infinity,
+    NaN,
+}
+
+/// A finite `numeric`, as `0.DIGITS * 10^exponent`, without leading or trailing zero digits.  Zero
+/// has no digits
+#[derive(PartialEq, Eq)]
+struct FiniteNumeric {
+    negative: bool,
+    exponent: i64,
+    digits: Vec<u8>,
+}
+
+impl NumericValue {
+    fn parse(text: &str) -> Option<NumericValue> {
+        let text = text.trim();
+        let (negative, unsigned) = match text.as_bytes().first() {
+            Some(b'-') => (true, &text[1..]),
+            Some(b'+') => (false, &text[1..]),
+            _ => (false, text),
+        };
+
+        if unsigned.eq_ignore_ascii_case("infinity") || unsigned.eq_ignore_ascii_case("inf") {
+            return Some(match negative {
+                true => NumericValue::NegativeInfinity,
+                false => NumericValue::Infinity,
+            });
+        } else if text.eq_ignore_ascii_case("nan") {
+            return Some(NumericValue::NaN);
+        }
+
+        let (mantissa, exponent) = match unsigned.find(|c| c == 'e' || c == 'E') {
+            Some(idx) => (&unsigned[..idx], unsigned[idx + 1..].parse::<i64>().ok()?),
+            None => (unsigned, 0),
+        };
+        let (integral, fractional) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let all_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if integral.len() + fractional.len() == 0
+            || !all_digits(integral)
+            || !all_digits(fractional)
+        {
+            return None;
+        }
+
+        let mut digits = integral.bytes().chain(fractional.bytes()).collect::<Vec<_>>();
+        let leading_zeros = digits.iter().take_while(|&&b| b == b'0').count();
+        digits.drain(..leading_zeros);
+        while digits.last() == Some(&b'0') {
+            digits.pop();
+        }
+        let exponent = exponent.checked_add(integral.len() as i64 - leading_zeros as i64)?;
+
+        Some(NumericValue::Finite(match digits.is_empty() {
+            true => FiniteNumeric { negative: false, exponent: 0, digits },
+            false => FiniteNumeric { negative, exponent, digits },
+        }))
+    }
+}
+
+impl PartialOrd for FiniteNumeric {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FiniteNumeric {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let signum = |n: &FiniteNumeric| match (n.negative, n.digits.is_empty()) {
+            (_, true) => 0,
+            (true, false) => -1,
+            (false, false) => 1,
+        };
+        match signum(self).cmp(&signum(other)) {
+            Ordering::Equal => {
+                let magnitude =
+                    self.exponent.cmp(&other.exponent).then_with(|| self.digits.cmp(&other.digits));
+                match self.negative {
+                    true => magnitude.reverse(),
+                    false => magnitude,
+                }
+            }
+            unequal => unequal,
+        }
+    }
+}
+
+/// Compare the text of two `numeric` values as Postgres' `numeric_cmp()` does
+fn numeric_cmp(left: &str, right: &str) -> Ordering {
+    match (NumericValue::parse(left), NumericValue::parse(right)) {
+        (Some(left), Some(right)) => left.cmp(&right),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => left.cmp(right),
+    }
+}
+
+impl IntoDatum for Numeric {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        self.as_datum()
+    }
+
     fn type_oid() -> u32 {
         pg_sys::NUMERICOID
     }
@@ -206,15 +404,103 @@ unsafe impl SqlTranslatable for Numeric {
     }
 }
 
-// impl<const PRECISION: i32, const SCALE: i32>
-//     SqlTranslatable
-//     for crate::datum::Numeric<PRECISION, SCALE>
-// {
-//     fn sql_type() -> String {
-//         if PRECISION == 0 && SCALE == 0 {
-//             String::from("NUMERIC")
-//         } else {
-//             format!("NUMERIC({PRECISION}, {SCALE})")
-//         }
-//     }
-// }
+/// A `NUMERIC(PRECISION, SCALE)` value, whose conversions to and from a Postgres `numeric` Datum
+/// apply the typmod, rounding values to `SCALE` decimal places and raising a
+/// "numeric field overflow" ERROR for values that don't fit within `PRECISION` digits
+///
+/// ```rust,no_run
+/// use pgx::FixedNumeric;
+///
+/// // rounds to `12.35` when converted to a Datum
+/// let price = FixedNumeric::<10, 2>("12.345".into());
+/// ```
+#[derive(Serialize, Debug)]
+pub struct FixedNumeric<const PRECISION: u32, const SCALE: u32>(pub String);
+
+impl<const PRECISION: u32, const SCALE: u32> FixedNumeric<PRECISION, SCALE> {
+    const TYPMOD: i32 = {
+        // `RangeInclusive::contains()` isn't a `const fn`, but a range pattern is usable here
+        assert!(matches!(PRECISION, 1..=1000), "NUMERIC precision must be between 1 and 1000");
+        assert!(SCALE <= PRECISION, "NUMERIC scale must not be greater than its precision");
+
+        // mirrors `make_numeric_typmod()` in Postgres' `utils/adt/numeric.c`
+        (((PRECISION << 16) | SCALE) as usize + pg_sys::VARHDRSZ) as i32
+    };
+
+    /// The Postgres typmod for `NUMERIC(PRECISION, SCALE)`
+    pub fn typmod() -> i32 {
+        Self::TYPMOD
+    }
+}
+
+impl<const PRECISION: u32, const SCALE: u32> std::fmt::Display for FixedNumeric<PRECISION, SCALE> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::result::Result<(), std::fmt::Error> {
+        fmt.write_fmt(format_args!("{}", self.0))
+    }
+}
+
+impl<const PRECISION: u32, const SCALE: u32> From<Numeric> for FixedNumeric<PRECISION, SCALE> {
+    fn from(val: Numeric) -> Self {
+        FixedNumeric(val.0)
+    }
+}
+
+impl<const PRECISION: u32, const SCALE: u32> From<FixedNumeric<PRECISION, SCALE>> for Numeric {
+    fn from(val: FixedNumeric<PRECISION, SCALE>) -> Self {
+        Numeric(val.0)
+    }
+}
+
+impl<const PRECISION: u32, const SCALE: u32> FromDatum for FixedNumeric<PRECISION, SCALE> {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        _typoid: u32,
+    ) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        if is_null {
+            None
+        } else {
+            // Postgres doesn't enforce typmods on function arguments, so we coerce the value here
+            let coerced = direct_function_call_as_datum(
+                pg_sys::numeric,
+                vec![Some(datum), Self::TYPMOD.into_datum()],
+            );
+            let cstr = direct_function_call::<&std::ffi::CStr>(pg_sys::numeric_out, vec![coerced])
+                .expect("numeric_out returned null");
+            Some(FixedNumeric(cstr.to_str().unwrap().into()))
+        }
+    }
+}
+
+impl<const PRECISION: u32, const SCALE: u32> IntoDatum for FixedNumeric<PRECISION, SCALE> {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        let cstring =
+            std::ffi::CString::new(self.0).expect("failed to convert numeric string into CString");
+        let cstr = cstring.as_c_str();
+
+        unsafe {
+            direct_function_call_as_datum(
+                pg_sys::numeric_in,
+                vec![cstr.into_datum(), pg_sys::InvalidOid.into_datum(), Self::TYPMOD.into_datum()],
+            )
+        }
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::NUMERICOID
+    }
+}
+
+unsafe impl<const PRECISION: u32, const SCALE: u32> SqlTranslatable
+    for FixedNumeric<PRECISION, SCALE>
+{
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::As(format!("NUMERIC({}, {})", PRECISION, SCALE)))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::As(format!("NUMERIC({}, {})", PRECISION, SCALE))))
+    }
+}
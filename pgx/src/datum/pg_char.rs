@@ -0,0 +1,95 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+use crate::{pg_sys, FromDatum, IntoDatum};
+use pgx_utils::sql_entity_graph::metadata::{
+    ArgumentError, Returns, ReturnsError, SqlMapping, SqlTranslatable,
+};
+use std::os::raw::c_char;
+
+/// A single byte, as Postgres' internal `"char"` type (note the quotes), which is not the same as
+/// SQL's `char(n)` (aka `bpchar`).
+///
+/// `"char"` is a C `char`, which is signed on some platforms and unsigned on others.  `PgChar`
+/// always treats the byte as unsigned, and stores it as the platform's C `char` so that its Datum
+/// compares equal to one Postgres made itself.
+///
+/// For historical reasons, a bare `i8` also maps to `"char"`.  Prefer `PgChar`, so it's clear
+/// that you mean a character and not a small integer, which is `i16` (`smallint`).  The mapping of
+/// Rust primitives to SQL types is:
+///
+/// | Rust    | SQL                |
+/// |---------|--------------------|
+/// | `bool`  | `bool`             |
+/// | `i8`    | `"char"`           |
+/// | `i16`   | `smallint`         |
+/// | `i32`   | `integer`          |
+/// | `i64`   | `bigint`           |
+/// | `f32`   | `real`             |
+/// | `f64`   | `double precision` |
+/// | `char`  | `varchar`          |
+/// | `u8`    | not supported      |
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct PgChar(pub u8);
+
+impl From<u8> for PgChar {
+    fn from(val: u8) -> Self {
+        PgChar(val)
+    }
+}
+
+impl From<PgChar> for u8 {
+    fn from(val: PgChar) -> Self {
+        val.0
+    }
+}
+
+impl From<PgChar> for char {
+    fn from(val: PgChar) -> Self {
+        val.0 as char
+    }
+}
+
+/// for `"char"`
+impl FromDatum for PgChar {
+    #[inline]
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        _: pg_sys::Oid,
+    ) -> Option<PgChar> {
+        if is_null {
+            None
+        } else {
+            Some(PgChar(datum.value() as u8))
+        }
+    }
+}
+
+/// for `"char"`
+impl IntoDatum for PgChar {
+    #[inline]
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        // mirrors Postgres' `CharGetDatum()`, which sign-extends where `char` is signed
+        Some(pg_sys::Datum::from(self.0 as c_char))
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::CHAROID
+    }
+}
+
+unsafe impl SqlTranslatable for PgChar {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::literal("\"char\""))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::literal("\"char\"")))
+    }
+}
@@ -0,0 +1,112 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+//! Conversions between `std::time` and Postgres' `timestamp with time zone` and `interval`
+
+use super::time_stamp_with_timezone::USECS_PER_SEC;
+use crate::{pg_sys, FromDatum, IntoDatum, PgBox, TimestampWithTimeZone};
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Postgres counts timestamps from 2000-01-01, which is this many seconds after the Unix epoch
+const PG_EPOCH_UNIX_SECS: u64 = 946_684_800;
+
+const USECS_PER_DAY: i64 = 86_400 * USECS_PER_SEC;
+
+fn pg_epoch() -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(PG_EPOCH_UNIX_SECS)
+}
+
+/// for `timestamp with time zone`.  Sub-microsecond precision is truncated
+impl IntoDatum for SystemTime {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        let usecs = match self.duration_since(pg_epoch()) {
+            Ok(after) => i64::try_from(after.as_micros()).ok(),
+            Err(before) => i64::try_from(before.duration().as_micros()).ok().map(|usecs| -usecs),
+        };
+
+        usecs
+            .and_then(|usecs| TimestampWithTimeZone::try_from(usecs).ok())
+            .filter(|tstz| !tstz.is_infinity() && !tstz.is_neg_infinity())
+            .expect("SystemTime is out of range for timestamp with time zone")
+            .into_datum()
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::TIMESTAMPTZOID
+    }
+}
+
+/// for `timestamp with time zone`.  Panics on `infinity` and `-infinity`, which `SystemTime`
+/// can't represent
+impl FromDatum for SystemTime {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        typoid: pg_sys::Oid,
+    ) -> Option<SystemTime> {
+        let tstz = TimestampWithTimeZone::from_polymorphic_datum(datum, is_null, typoid)?;
+        if tstz.is_infinity() || tstz.is_neg_infinity() {
+            panic!("an infinite timestamp with time zone can't be represented as a SystemTime")
+        }
+
+        let usecs = i64::from(tstz);
+        let offset = Duration::from_micros(usecs.unsigned_abs());
+        Some(if usecs >= 0 { pg_epoch() + offset } else { pg_epoch() - offset })
+    }
+}
+
+/// for `interval`.  Sub-microsecond precision is truncated, and the whole `Duration` is stored
+/// in the interval's time component, so it's never justified into days or months
+impl IntoDatum for Duration {
+    fn into_datum(self) -> Option<pg_sys::Datum> {
+        let usecs = i64::try_from(self.as_micros()).expect("Duration is out of range for interval");
+
+        let mut interval = PgBox::<pg_sys::Interval>::alloc0();
+        interval.time = usecs;
+        Some(interval.into_pg().into())
+    }
+
+    fn type_oid() -> u32 {
+        pg_sys::INTERVALOID
+    }
+}
+
+/// for `interval`.  Each day is counted as 24 hours.
+///
+/// Panics if the interval is negative, or has a month component, as months don't have a fixed
+/// length.  Use `justify_hours()` or `date_trunc()` in SQL first, if need be
+impl FromDatum for Duration {
+    unsafe fn from_polymorphic_datum(
+        datum: pg_sys::Datum,
+        is_null: bool,
+        _: pg_sys::Oid,
+    ) -> Option<Duration> {
+        if is_null {
+            return None;
+        }
+
+        let interval = datum.cast_mut_ptr::<pg_sys::Interval>().as_ref().expect("interval is NULL");
+        if interval.month != 0 {
+            panic!(
+                "an interval of {} month(s) can't be represented as a Duration, as months vary in length",
+                interval.month
+            )
+        }
+
+        let usecs = (interval.day as i64)
+            .checked_mul(USECS_PER_DAY)
+            .and_then(|days| days.checked_add(interval.time))
+            .expect("interval is out of range for Duration");
+        match u64::try_from(usecs) {
+            Ok(usecs) => Some(Duration::from_micros(usecs)),
+            Err(_) => panic!("a negative interval can't be represented as a Duration"),
+        }
+    }
+}
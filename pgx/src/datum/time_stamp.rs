@@ -14,6 +14,12 @@ use pgx_utils::sql_entity_graph::metadata::{
 use serde::Deserialize;
 use std::ffi::CStr;
 
+/// A Postgres `timestamp`, that is, `timestamp without time zone`: a wall-clock date and time,
+/// in no particular time zone.
+///
+/// Unlike a [`TimestampWithTimeZone`], it doesn't identify a single instant, and the session's
+/// `TimeZone` setting plays no part in storing or displaying it.  Converting between the two, as
+/// Postgres' casts do, interprets the wall-clock time in the session's `TimeZone`
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
 #[repr(transparent)]
 pub struct Timestamp(pg_sys::Timestamp);
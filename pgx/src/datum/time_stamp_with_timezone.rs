@@ -27,6 +27,10 @@ const PG_EPOCH_DATETIME: time::PrimitiveDateTime = date!(2000 - 01 - 01).midnigh
 const MIN_TIMESTAMP_USEC: i64 = -211_813_488_000_000_000;
 const END_TIMESTAMP_USEC: i64 = 9_223_371_331_200_000_000 - 1; // dec by 1 to accommodate exclusive range match pattern
 
+/// A Postgres `timestamptz`, that is, `timestamp with time zone`: a single instant, stored as UTC.
+///
+/// Postgres displays it in the session's `TimeZone`.  For a wall-clock date and time without
+/// time zone semantics, use a [`Timestamp`](crate::Timestamp) instead
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
 #[repr(transparent)]
 pub struct TimestampWithTimeZone(pg_sys::TimestampTz);
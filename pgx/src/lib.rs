@@ -35,6 +35,7 @@ pub mod prelude;
 
 pub mod aggregate;
 pub mod callbacks;
+pub mod compat;
 pub mod datum;
 pub mod enum_helper;
 pub mod fcinfo;
@@ -56,6 +57,7 @@ pub mod memcxt;
 pub mod misc;
 pub mod namespace;
 pub mod nodes;
+pub mod notify;
 pub mod pgbox;
 pub mod rel;
 pub mod shmem;
@@ -59,7 +59,7 @@ pub enum PgLogLevel {
 
 /// This list of SQL Error Codes is taken directly from Postgres 12's generated "utils/errcodes.h"
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum PgSqlErrorCode {
     /// Class 00 - Successful Completion
     ERRCODE_SUCCESSFUL_COMPLETION = MAKE_SQLSTATE('0', '0', '0', '0', '0') as isize,
@@ -433,6 +433,14 @@ pub enum PgSqlErrorCode {
     ERRCODE_INDEX_CORRUPTED = MAKE_SQLSTATE('X', 'X', '0', '0', '2') as isize,
 }
 
+/// The SQLSTATE error code as Postgres represents it, such as is returned from
+/// [`PgTryResult::sqlerrcode()`][crate::pg_sys::PgTryResult::sqlerrcode]
+impl From<PgSqlErrorCode> for i32 {
+    fn from(code: PgSqlErrorCode) -> Self {
+        code as i32
+    }
+}
+
 #[allow(non_snake_case)]
 #[inline]
 const fn PGSIXBIT(ch: i32) -> i32 {
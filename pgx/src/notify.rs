@@ -0,0 +1,31 @@
+/*
+Portions Copyright 2019-2021 ZomboDB, LLC.
+Portions Copyright 2021-2022 Technology Concepts & Design, Inc. <support@tcdi.com>
+
+All rights reserved.
+
+Use of this source code is governed by the MIT license that can be found in the LICENSE file.
+*/
+
+//! Helpers for Postgres' asynchronous notifications, ie, `NOTIFY`
+//!
+//! Like `NOTIFY`, sending is transactional:  notifications are only sent once the current
+//! transaction commits.
+//!
+//! There are deliberately no `LISTEN` helpers.  Postgres only delivers notifications to the
+//! *client* of a listening session, and a background worker has no client, so any it received
+//! would simply be logged by Postgres rather than handed to the worker.
+use crate::{direct_function_call_as_datum, pg_sys, IntoDatum};
+
+/// Send a notification on `channel` with the given `payload`.  Same as Postgres'
+/// `pg_notify(text, text)` function.
+///
+/// Raises an ERROR if `channel` is empty or too long, or if `payload` is too long
+pub fn pg_notify(channel: &str, payload: &str) {
+    unsafe {
+        direct_function_call_as_datum(
+            pg_sys::pg_notify,
+            vec![channel.into_datum(), payload.into_datum()],
+        );
+    }
+}
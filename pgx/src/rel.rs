@@ -216,6 +216,129 @@ impl PgRelation {
         PgTupleDesc::from_relation(&self)
     }
 
+    /// Insert a row into this table, given `(column name, value)` pairs, and return the new
+    /// tuple's `ctid`.  Columns that aren't named are `NULL`, not their `DEFAULT`.
+    ///
+    /// The tuple is written with `pg_sys::simple_heap_insert()` and an entry is added to each of
+    /// the table's indices, checking uniqueness where required.  `CHECK` constraints, foreign
+    /// keys, and triggers are **not** run, and the insert is made visible to later commands with
+    /// `pg_sys::CommandCounterIncrement()`.
+    ///
+    /// Panics if a column doesn't exist, a `NOT NULL` column is `NULL`, or the table has an index
+    /// on an expression, a partial index, an exclusion constraint, or a deferrable unique
+    /// constraint, which can't be maintained without the executor.
+    ///
+    /// ```rust,no_run
+    /// use pgx::{direct_function_call, prelude::*, IntoDatum, PgRelation};
+    /// let oid = unsafe {
+    ///     direct_function_call::<pg_sys::Oid>(pg_sys::to_regclass, vec!["dogs".into_datum()])
+    /// }
+    /// .expect("no such relation");
+    /// let relation = PgRelation::with_lock(oid, pg_sys::RowExclusiveLock as pg_sys::LOCKMODE);
+    /// unsafe {
+    ///     relation.insert(&[("name", "Brandy".into_datum()), ("age", 3i32.into_datum())]);
+    /// }
+    /// ```
+    ///
+    /// ## Safety
+    ///
+    /// Each `Datum` must be of its column's type, and the caller should hold at least
+    /// `RowExclusiveLock` on the table
+    pub unsafe fn insert(
+        &self,
+        values: &[(&str, Option<pg_sys::Datum>)],
+    ) -> pg_sys::ItemPointerData {
+        if !self.is_table() {
+            panic!("`{}` is not a table", self.name());
+        }
+
+        let tupdesc = self.tuple_desc();
+        let natts = tupdesc.len();
+        let mut datums = vec![pg_sys::Datum::from(0); natts];
+        let mut nulls = vec![true; natts];
+
+        for (name, value) in values {
+            let attno = tupdesc
+                .iter()
+                .position(|att| !att.attisdropped && name_data_to_str(&att.attname) == *name)
+                .unwrap_or_else(|| {
+                    panic!("column `{}` of relation `{}` does not exist", name, self.name())
+                });
+            if let Some(datum) = value {
+                datums[attno] = *datum;
+                nulls[attno] = false;
+            }
+        }
+
+        for (att, is_null) in tupdesc.iter().zip(nulls.iter()) {
+            if att.attnotnull && *is_null {
+                panic!(
+                    "null value in column `{}` of relation `{}` violates not-null constraint",
+                    name_data_to_str(&att.attname),
+                    self.name()
+                );
+            }
+        }
+
+        // check every index up front, so we don't panic with the heap tuple half-indexed
+        let indices = self
+            .indicies(pg_sys::RowExclusiveLock as pg_sys::LOCKMODE)
+            .filter(|index| {
+                let rd_index = index.rd_index.as_ref().expect("rd_index is NULL");
+                rd_index.indisready
+            })
+            .collect::<Vec<_>>();
+        for index in &indices {
+            let rd_index = index.rd_index.as_ref().expect("rd_index is NULL");
+            if rd_index.indisexclusion || (rd_index.indisunique && !rd_index.indimmediate) {
+                panic!(
+                    "index `{}` is an exclusion or deferrable constraint, which PgRelation::insert() does not support",
+                    index.name()
+                );
+            }
+            if !pg_sys::RelationGetIndexExpressions(index.as_ptr()).is_null()
+                || !pg_sys::RelationGetIndexPredicate(index.as_ptr()).is_null()
+            {
+                panic!(
+                    "index `{}` is an expression or partial index, which PgRelation::insert() does not support",
+                    index.name()
+                );
+            }
+        }
+
+        let tuple =
+            pg_sys::heap_form_tuple(tupdesc.as_ptr(), datums.as_mut_ptr(), nulls.as_mut_ptr());
+        pg_sys::simple_heap_insert(self.as_ptr(), tuple);
+        let ctid = &mut (*tuple).t_self as pg_sys::ItemPointer;
+
+        for index in &indices {
+            let rd_index = index.rd_index.as_ref().expect("rd_index is NULL");
+            let keys = rd_index.indkey.values.as_slice(rd_index.indnatts as usize);
+            let mut index_datums =
+                keys.iter().map(|attnum| datums[*attnum as usize - 1]).collect::<Vec<_>>();
+            let mut index_nulls =
+                keys.iter().map(|attnum| nulls[*attnum as usize - 1]).collect::<Vec<_>>();
+            let check_unique = if rd_index.indisunique {
+                pg_sys::IndexUniqueCheck_UNIQUE_CHECK_YES
+            } else {
+                pg_sys::IndexUniqueCheck_UNIQUE_CHECK_NO
+            };
+
+            crate::compat::index_insert(
+                index.as_ptr(),
+                index_datums.as_mut_ptr(),
+                index_nulls.as_mut_ptr(),
+                ctid,
+                self.as_ptr(),
+                check_unique,
+                pg_sys::BuildIndexInfo(index.as_ptr()),
+            );
+        }
+
+        pg_sys::CommandCounterIncrement();
+        *ctid
+    }
+
     /// Number of tuples in this relation (not always up-to-date)
     pub fn reltuples(&self) -> Option<f32> {
         let reltuples = unsafe { self.boxed.rd_rel.as_ref() }.expect("rd_rel is NULL").reltuples;
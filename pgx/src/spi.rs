@@ -64,6 +64,17 @@ pub enum SpiError {
     RelNotFound = -13,
 }
 
+/// Why [`SpiHeapTupleData::get_by_name()`] or [`SpiHeapTupleData::get_by_ordinal()`] couldn't get
+/// a field's value
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum SpiTupleError {
+    #[error("SPI error: {0:?}")]
+    Spi(#[from] SpiError),
+
+    #[error("Incompatible field type, found oid {found}, requested type has oid {expected}")]
+    TypeMismatch { expected: pg_sys::Oid, found: pg_sys::Oid },
+}
+
 #[derive(Debug)]
 pub struct UnknownVariant;
 
@@ -115,6 +126,27 @@ pub struct SpiTupleTable {
     current: isize,
 }
 
+/// A cursor over the results of a query, opened with [`SpiClient::open_cursor`]
+pub struct SpiCursor<'client> {
+    portal: pg_sys::Portal,
+    __marker: std::marker::PhantomData<&'client SpiClient>,
+}
+
+/// A query whose positional arguments (`$1`, `$2`, etc) are bound, in order, from Rust values
+///
+/// Created with [`Spi::query`].
+///
+/// ```rust,no_run
+/// use pgx::prelude::*;
+///
+/// let sum = Spi::query("SELECT $1 + $2").bind(40i32).bind(2i32).get_one::<i32>();
+/// assert_eq!(sum, Some(42));
+/// ```
+pub struct SpiQuery {
+    query: String,
+    args: Vec<(PgOid, Option<pg_sys::Datum>)>,
+}
+
 /// Represents a single `pg_sys::Datum` inside a `SpiHeapTupleData`
 pub struct SpiHeapTupleDataEntry {
     datum: Option<pg_sys::Datum>,
@@ -193,6 +225,11 @@ impl Spi {
         .unwrap()
     }
 
+    /// start building a query whose arguments are bound from Rust values.  See [`SpiQuery`]
+    pub fn query(query: &str) -> SpiQuery {
+        SpiQuery { query: query.to_string(), args: Vec::new() }
+    }
+
     /// just run an arbitrary SQL statement.
     ///
     /// ## Safety
@@ -319,6 +356,61 @@ impl Spi {
     }
 }
 
+impl SpiQuery {
+    /// bind `value` as the next positional argument of the query, using its [`IntoDatum::type_oid`]
+    /// as the argument's type
+    pub fn bind<T: IntoDatum>(mut self, value: T) -> Self {
+        self.args.push((PgOid::from(T::type_oid()), value.into_datum()));
+        self
+    }
+
+    /// run the query, returning the first column of its first row
+    pub fn get_one<A: FromDatum + IntoDatum>(self) -> Option<A> {
+        Spi::get_one_with_args(&self.query, self.args)
+    }
+
+    /// run the query, returning the first two columns of its first row
+    pub fn get_two<A: FromDatum + IntoDatum, B: FromDatum + IntoDatum>(
+        self,
+    ) -> (Option<A>, Option<B>) {
+        Spi::get_two_with_args(&self.query, self.args)
+    }
+
+    /// run the query, returning the first three columns of its first row
+    pub fn get_three<
+        A: FromDatum + IntoDatum,
+        B: FromDatum + IntoDatum,
+        C: FromDatum + IntoDatum,
+    >(
+        self,
+    ) -> (Option<A>, Option<B>, Option<C>) {
+        Spi::get_three_with_args(&self.query, self.args)
+    }
+
+    /// run the query, returning the first column of every row
+    pub fn get<A: FromDatum + IntoDatum>(self) -> Vec<Option<A>> {
+        let SpiQuery { query, args } = self;
+        Spi::connect(|client| {
+            let values = client
+                .select(&query, None, Some(args))
+                .map(|row| row.by_ordinal(1).ok().and_then(|entry| entry.value::<A>()))
+                .collect::<Vec<Option<A>>>();
+            Ok(Some(values))
+        })
+        .unwrap_or_default()
+    }
+
+    /// run the query using an existing `SpiClient`, returning its rows for iteration
+    pub fn iter(self, client: &SpiClient) -> SpiTupleTable {
+        client.select(&self.query, None, Some(self.args))
+    }
+
+    /// run the query, ignoring any results.  The statement runs in read/write mode
+    pub fn run(self) {
+        Spi::run_with_args(&self.query, Some(self.args))
+    }
+}
+
 impl SpiClient {
     /// perform a SELECT statement
     pub fn select(
@@ -365,27 +457,7 @@ impl SpiClient {
         let status_code = match args {
             Some(args) => {
                 let nargs = args.len();
-                let mut argtypes = vec![];
-                let mut datums = vec![];
-                let mut nulls = vec![];
-
-                for (argtype, datum) in args {
-                    argtypes.push(argtype.value());
-
-                    match datum {
-                        Some(datum) => {
-                            // ' ' here means that the datum is not null
-                            datums.push(datum);
-                            nulls.push(' ' as std::os::raw::c_char);
-                        }
-
-                        None => {
-                            // 'n' here means that the datum is null
-                            datums.push(pg_sys::Datum::from(0usize));
-                            nulls.push('n' as std::os::raw::c_char);
-                        }
-                    }
-                }
+                let (mut argtypes, mut datums, nulls) = SpiClient::prepare_args(args);
 
                 unsafe {
                     pg_sys::SPI_execute_with_args(
@@ -414,6 +486,110 @@ impl SpiClient {
             current: -1,
         }
     }
+
+    /// Open a cursor over the results of `query`, so they can be [`SpiCursor::fetch`]ed in
+    /// batches rather than materialized all at once.  The cursor is closed when dropped
+    ///
+    /// ```rust,no_run
+    /// use pgx::prelude::*;
+    ///
+    /// let sum = Spi::connect(|client| {
+    ///     let mut cursor = client.open_cursor("SELECT * FROM generate_series(1, 10000)", None);
+    ///     let mut sum = 0i64;
+    ///     loop {
+    ///         let batch = cursor.fetch(1000);
+    ///         if batch.is_empty() {
+    ///             break;
+    ///         }
+    ///         for row in batch {
+    ///             sum += row.by_ordinal(1).unwrap().value::<i32>().unwrap() as i64;
+    ///         }
+    ///     }
+    ///     Ok(Some(sum))
+    /// });
+    /// ```
+    pub fn open_cursor(
+        &self,
+        query: &str,
+        args: Option<Vec<(PgOid, Option<pg_sys::Datum>)>>,
+    ) -> SpiCursor<'_> {
+        let src = std::ffi::CString::new(query).expect("query contained a null byte");
+        let args = args.unwrap_or_default();
+        let nargs = args.len();
+        let (mut argtypes, mut datums, nulls) = SpiClient::prepare_args(args);
+
+        let portal = unsafe {
+            pg_sys::SPI_cursor_open_with_args(
+                std::ptr::null(),
+                src.as_ptr(),
+                nargs as i32,
+                argtypes.as_mut_ptr(),
+                datums.as_mut_ptr(),
+                nulls.as_ptr(),
+                false,
+                0,
+            )
+        };
+
+        SpiCursor { portal, __marker: std::marker::PhantomData }
+    }
+
+    fn prepare_args(
+        args: Vec<(PgOid, Option<pg_sys::Datum>)>,
+    ) -> (Vec<pg_sys::Oid>, Vec<pg_sys::Datum>, Vec<std::os::raw::c_char>) {
+        let mut argtypes = vec![];
+        let mut datums = vec![];
+        let mut nulls = vec![];
+
+        for (argtype, datum) in args {
+            argtypes.push(argtype.value());
+
+            match datum {
+                Some(datum) => {
+                    // ' ' here means that the datum is not null
+                    datums.push(datum);
+                    nulls.push(' ' as std::os::raw::c_char);
+                }
+
+                None => {
+                    // 'n' here means that the datum is null
+                    datums.push(pg_sys::Datum::from(0usize));
+                    nulls.push('n' as std::os::raw::c_char);
+                }
+            }
+        }
+
+        (argtypes, datums, nulls)
+    }
+}
+
+impl<'client> SpiCursor<'client> {
+    /// Fetch up to `count` more rows from the cursor.  An empty [`SpiTupleTable`] means the
+    /// cursor is exhausted
+    pub fn fetch(&mut self, count: i64) -> SpiTupleTable {
+        unsafe {
+            pg_sys::SPI_tuptable = std::ptr::null_mut();
+            pg_sys::SPI_cursor_fetch(self.portal, true, count);
+        }
+
+        SpiTupleTable {
+            status_code: SpiOk::Fetch,
+            table: unsafe { pg_sys::SPI_tuptable },
+            size: unsafe { pg_sys::SPI_processed as usize },
+            tupdesc: if unsafe { pg_sys::SPI_tuptable }.is_null() {
+                None
+            } else {
+                Some(unsafe { (*pg_sys::SPI_tuptable).tupdesc })
+            },
+            current: -1,
+        }
+    }
+}
+
+impl<'client> Drop for SpiCursor<'client> {
+    fn drop(&mut self) {
+        unsafe { pg_sys::SPI_cursor_close(self.portal) }
+    }
 }
 
 impl SpiTupleTable {
@@ -612,6 +788,32 @@ impl SpiHeapTupleData {
         }
     }
 
+    /// Get the value of the field at this ordinal position as a `T`.
+    ///
+    /// The ordinal position is 1-based.
+    ///
+    /// A NULL value is `Ok(None)`.  If the specified ordinal is out of bounds a
+    /// `Err(SpiTupleError::Spi(SpiError::NoAttribute))` is returned, and if the field's type isn't
+    /// compatible with `T` a `Err(SpiTupleError::TypeMismatch { .. })`
+    pub fn get_by_ordinal<T: IntoDatum + FromDatum>(
+        &self,
+        ordinal: usize,
+    ) -> std::result::Result<Option<T>, SpiTupleError> {
+        self.by_ordinal(ordinal)?.checked_value()
+    }
+
+    /// Get the value of the field with this name as a `T`.
+    ///
+    /// A NULL value is `Ok(None)`.  If the specified name does not exist a
+    /// `Err(SpiTupleError::Spi(SpiError::NoAttribute))` is returned, and if the field's type isn't
+    /// compatible with `T` a `Err(SpiTupleError::TypeMismatch { .. })`
+    pub fn get_by_name<T: IntoDatum + FromDatum>(
+        &self,
+        name: &str,
+    ) -> std::result::Result<Option<T>, SpiTupleError> {
+        self.by_name(name)?.checked_value()
+    }
+
     /// Get a mutable typed Datum value from this HeapTuple by its ordinal position.  
     ///
     /// The ordinal position is 1-based.
@@ -703,6 +905,16 @@ impl SpiHeapTupleDataEntry {
     pub fn oid(&self) -> pg_sys::Oid {
         self.type_oid
     }
+
+    fn checked_value<T: IntoDatum + FromDatum>(
+        &self,
+    ) -> std::result::Result<Option<T>, SpiTupleError> {
+        if T::is_compatible_with(self.type_oid) {
+            Ok(self.value())
+        } else {
+            Err(SpiTupleError::TypeMismatch { expected: T::type_oid(), found: self.type_oid })
+        }
+    }
 }
 
 /// Provide ordinal indexing into a `SpiHeapTupleData`.
@@ -9,26 +9,9 @@ Use of this source code is governed by the MIT license that can be found in the
 
 use crate::pg_sys;
 
-#[cfg(any(feature = "pg10", feature = "pg11"))]
 #[inline]
 pub fn xid_to_64bit(xid: pg_sys::TransactionId) -> u64 {
-    let mut last_xid = pg_sys::InvalidTransactionId;
-    let mut epoch = 0u32;
-
-    unsafe {
-        pg_sys::GetNextXidAndEpoch(&mut last_xid, &mut epoch);
-    }
-
-    convert_xid_common(xid, last_xid, epoch)
-}
-
-#[cfg(any(feature = "pg12", feature = "pg13", feature = "pg14"))]
-#[inline]
-pub fn xid_to_64bit(xid: pg_sys::TransactionId) -> u64 {
-    let full_xid = unsafe { pg_sys::ReadNextFullTransactionId() };
-
-    let last_xid = full_xid.value as u32;
-    let epoch = (full_xid.value >> 32) as u32;
+    let (last_xid, epoch) = crate::compat::next_xid_and_epoch();
 
     convert_xid_common(xid, last_xid, epoch)
 }